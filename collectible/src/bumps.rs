@@ -12,3 +12,27 @@ pub(crate) const BALANCES_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
 
 pub(crate) const ITEMS_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
 pub(crate) const ITEMS_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const OFFERS_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const OFFERS_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const AUCTIONS_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const AUCTIONS_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const SWAPS_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const SWAPS_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const BUNDLES_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const BUNDLES_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const PRIMARY_PURCHASES_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const PRIMARY_PURCHASES_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const PRICE_OVERRIDES_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const PRICE_OVERRIDES_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const VOUCHERS_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const VOUCHERS_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
+
+pub(crate) const ESCROWED_SALES_BUMP_CONSTANT: u32 = DAY_IN_LEDGERS * 28;
+pub(crate) const ESCROWED_SALES_BUMP_CONSTANT_THRESHOLD: u32 = DAY_IN_LEDGERS * 14;
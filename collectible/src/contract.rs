@@ -1,20 +1,69 @@
-use crate::errors::SCErrors;
-use crate::storage::core::{CoreData, TokenMetadata};
-use crate::storage::items::Item;
-use crate::storage::royalties::Royalty;
+use crate::errors::{SCErrors, SCRoyaltyErrors};
+use crate::storage::auctions::Auction;
+use crate::storage::bundles::Bundle;
+use crate::storage::core::{CoreData, PrimarySale, TokenMetadata};
+use crate::storage::items::{Item, Listing, Reservation};
+use crate::storage::offers::{CollectionBid, Offer};
+use crate::storage::royalties::{
+    ItemRoyaltiesUpdate, PendingItemRoyalties, PendingRoyalties, Royalty,
+};
+use crate::storage::sales::{
+    BondingCurve, BondingCurveKind, DutchAuction, EscrowedSale, PriceTier, ReservedItems, SalePhase,
+};
+use crate::storage::swaps::{Swap, SwapRoyaltyPolicy};
+use crate::storage::vouchers::Voucher;
+use crate::utils::auctions::{
+    bump_auction, bump_bid_refund, get_auction, get_bid_refund, has_bids, min_next_bid,
+    remove_auction, remove_bid_refund, write_auction, write_bid_refund, AUCTION_EXTENSION_LEDGERS,
+};
 use crate::utils::balances::{bump_balance, get_balance, write_balance};
+use crate::utils::bundles::{bump_bundle, get_bundle, next_bundle_id, remove_bundle, write_bundle};
 use crate::utils::core::{
-    bump_instance, collection_currency, get_core_data, get_metadata, is_initialized,
-    write_core_data, write_token_metadata,
+    bump_instance, collection_currency, get_core_data, get_metadata, get_metadata_commitment,
+    get_primary_sale, is_initialized, is_metadata_revealed, metadata_commitment_hash,
+    write_core_data, write_metadata_commitment, write_metadata_revealed, write_primary_sale,
+    write_token_metadata,
 };
 use crate::utils::items::{
-    bump_item, get_item, is_item_for_sale, is_minted, is_valid_item_number, write_item,
+    bump_item, change_owner, check_item_is_not_locked, get_item, get_listing, get_minted_count,
+    get_owned_items, has_minted_count, is_item_for_sale, is_minted, is_valid_item_number,
+    mint_item, pick_random_unminted_item, remove_listing, remove_unminted_item, unmint_item,
+    write_item, write_listing, write_minted_count,
+};
+use crate::utils::offers::{
+    bump_collection_bid, bump_offer, get_collection_bid, get_offer, has_collection_bid, has_offer,
+    remove_collection_bid, remove_offer, write_collection_bid, write_offer,
+};
+use crate::utils::royalties::{
+    bump_item_royalties, bump_pending_item_royalties, bump_pending_royalties, bump_royalties,
+    check_royalties_are_not_frozen, get_item_royalties, get_pending_item_royalties,
+    get_pending_royalties, get_royalties, get_royalties_delay, is_royalties_frozen,
+    migrate_royalties, remove_pending_item_royalties, remove_pending_royalties, royalty_shares,
+    validate_royalties, write_item_royalties, write_pending_item_royalties,
+    write_pending_royalties, write_royalties, write_royalties_delay, write_royalties_frozen,
+    write_royalties_version, ROYALTIES_VERSION,
+};
+use crate::utils::sales::{
+    bonding_curve_price, bump_escrowed_sale, bump_price_override, bump_primary_purchases,
+    collect_primary_payouts, collect_sale_payouts, collect_split_sale_payouts, get_bonding_curve,
+    get_escrowed_sale, get_max_per_wallet, get_price_tiers, get_primary_price,
+    get_primary_purchases, get_reserved_items, get_sale_phases, purchase_item,
+    remove_bonding_curve, remove_dutch_auction, remove_escrowed_sale, send_payouts,
+    settle_escrowed_sale, write_bonding_curve, write_dutch_auction, write_max_per_wallet,
+    write_price_override, write_price_tiers, write_refund_window, write_reserved_items,
+    write_sale_phases,
+};
+use crate::utils::swaps::{
+    bump_swap, get_swap, get_swap_royalty_policy, next_swap_id, remove_swap, write_swap,
+    write_swap_royalty_policy,
+};
+use crate::utils::vouchers::{
+    get_voucher_signer, is_nonce_used, write_used_nonce, write_voucher_signer,
 };
-use crate::utils::royalties::{bump_royalties, get_royalties, write_royalties};
-use num_integer::div_floor;
+use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
-    contract, contractimpl, panic_with_error, symbol_short, Address, BytesN, Env, String, Symbol,
-    Vec,
+    contract, contractimpl, panic_with_error, symbol_short, Address, Bytes, BytesN, Env, Map,
+    String, Symbol, Vec,
 };
 
 use crate::events;
@@ -22,6 +71,9 @@ use crate::events;
 pub trait CollectibleTrait {
     /// This function starts the contract with data that can't be updated later without doing a full upgrade
     /// If this function hasn't been called, most functions won't work
+    /// For blind drops, "metadata_commitment" is the sha256 hash of the final metadata URI bytes followed by the salt
+    /// bytes and the metadata URI is a placeholder until "reveal" is called. The commitment can only be set here so
+    /// it's fixed before anyone can buy
     fn init(
         env: Env,
         admin: Address,
@@ -29,10 +81,9 @@ pub trait CollectibleTrait {
         initial_price: u128,
         initial_seller: Address,
         collection_currency: Address,
-        name: String,
-        symbol: String,
-        metadata_uri: String,
+        token_metadata: TokenMetadata,
         royalties: Vec<Royalty>,
+        metadata_commitment: Option<BytesN<32>>,
     );
 
     fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
@@ -45,18 +96,74 @@ pub trait CollectibleTrait {
     fn balance(env: Env, id: Address) -> u128;
 
     /// The function to buy items that are for sale, if it's the "first sale" of the item, the contract will send the payment to the "initial_seller"
+    /// The price of the "first sale" is the current primary price, see "primary_price"
     /// If an item is not for sale it will throw an error
-    fn buy(env: Env, buyer: Address, item_number: u64);
+    /// If the price of the item is higher than "max_price" it will throw an error, this protects the buyer from price
+    /// changes made before the transaction is executed
+    /// If the active sale phase has an allowlist, a "first sale" needs the Merkle proof of the buyer
+    fn buy(
+        env: Env,
+        buyer: Address,
+        item_number: u64,
+        max_price: u128,
+        proof: Option<Vec<BytesN<32>>>,
+    );
+
+    /// Buys all the items in the list or none of them, it works for both "first sales" and items that are for sale
+    /// The payments are grouped so each recipient receives a single transfer
+    /// If the total price is higher than "max_total" it will throw an error
+    fn buy_many(
+        env: Env,
+        buyer: Address,
+        item_numbers: Vec<u64>,
+        max_total: u128,
+        proof: Option<Vec<BytesN<32>>>,
+    );
+
+    /// Buys a random Item that hasn't been minted yet, the Item is picked using the PRNG of the network
+    /// The PRNG is not hidden from the caller, a contract calling this function can check which Item it got and revert
+    /// until it gets the one it wants. The draw only stops accounts from choosing their Item when they call it directly
+    /// The rest of the rules are the same as in the "buy" function, it returns the number of the Item bought
+    fn buy_random(env: Env, buyer: Address, max_price: u128, proof: Option<Vec<BytesN<32>>>)
+        -> u64;
+
+    /// Sets the number of ledgers the payment of a primary purchase is kept in the contract, during that time the buyer
+    /// can ask for a refund. Only the admin can do this, "0" disables the refunds
+    /// The escrowed payments are kept alive for the whole window, up to the maximum TTL the network allows
+    fn set_refund_window(env: Env, ledgers: u32);
+
+    /// Returns an Item bought in the primary sale and refunds its price, the buyer must still own the Item and the
+    /// refund window must be open. The Item goes back to the primary sale
+    fn refund(env: Env, buyer: Address, item_number: u64);
+
+    /// Sends the payments of the primary purchases whose refund window has closed to the initial_seller and the
+    /// royalties the Items had when they were bought, anyone can call this function
+    fn release_proceeds(env: Env, item_numbers: Vec<u64>);
+
+    /// Returns the escrowed payment of a primary purchase, if it doesn't exist it will throw an error
+    fn escrowed_sale(env: Env, item_number: u64) -> EscrowedSale;
 
     /// Use this function when you want to offer one of your Items
     /// You must be the owner of the Item
     /// Setting the price to "0" is equal to cancelling the offer
-    fn sell(env: Env, item_number: u64, price: u128);
+    /// If an expiration ledger is provided, the Item stops being for sale once the ledger sequence is higher than it
+    /// If "reserved_for" is provided, only that Address can buy the Item
+    fn sell(
+        env: Env,
+        item_number: u64,
+        price: u128,
+        expiration_ledger: Option<u32>,
+        reserved_for: Option<Address>,
+    );
 
     /// Returns the specific Item, if the item hasn't been sold for the first time it will throw an error
-    /// This function can be used to know if an Item is currently for sale
+    /// This function can be used to know if an Item is currently for sale, "for_sale" is returned as false once the
+    /// listing has expired even if the stored Item wasn't updated yet
     fn item(env: Env, number: u64) -> Item;
 
+    /// Returns the conditions (expiration, reserved buyer) of the Item sale offer
+    fn listing(env: Env, number: u64) -> Listing;
+
     /// Transferring the ownership of a collectible
     /// The owner of the collectible is used as required authorization
     /// This function doesn't trigger the royalty payments
@@ -67,19 +174,296 @@ pub trait CollectibleTrait {
     /// This function doesn't trigger the royalty payments
     fn mint(env: Env, item_number: u64, to: Address);
 
+    /// Registers the ed25519 public key used to sign the Vouchers, only the initial_seller can do this
+    fn set_voucher_signer(env: Env, public_key: BytesN<32>);
+
+    /// Mints the Item of the Voucher to the recipient, the Voucher must be signed by the registered key
+    /// If the Voucher has a price the recipient pays it to the initial_seller, this counts as the "first sale" so the
+    /// first sale royalties are paid. Like any other primary purchase, the payment is kept while the refund window is
+    /// open
+    fn redeem_voucher(env: Env, recipient: Address, voucher: Voucher, signature: BytesN<64>);
+
+    /// Replaces the ranges of Items withheld from the primary sale, only the admin or the initial_seller can do this
+    /// Reserved Items are not for sale until they are minted with the "mint" function
+    /// Only the ranges are stored, random purchases draw again when they pick a reserved Item
+    /// Sending an empty list removes the reservations
+    fn set_reserved_items(env: Env, caller: Address, reserved_items: Vec<ReservedItems>);
+
+    fn reserved_items(env: Env) -> Vec<ReservedItems>;
+
+    /// Makes an offer to buy an Item that has already been minted, the amount is sent to the contract and kept there until
+    /// the offer is accepted or cancelled
+    /// If the bidder already had an offer for the same Item, the previous amount is refunded and the offer is replaced
+    fn make_offer(
+        env: Env,
+        bidder: Address,
+        item_number: u64,
+        amount: u128,
+        expiration_ledger: u32,
+    );
+
+    /// Cancels an offer and refunds the escrowed amount to the bidder, expired offers also need to be cancelled this way
+    fn cancel_offer(env: Env, bidder: Address, item_number: u64);
+
+    /// The owner of the Item accepts the offer made by the bidder
+    /// Royalties are distributed the same way they are when someone buys the Item
+    fn accept_offer(env: Env, item_number: u64, bidder: Address);
+
+    /// Returns the offer a bidder has made for an Item, if it doesn't exist it will throw an error
+    fn offer(env: Env, item_number: u64, bidder: Address) -> Offer;
+
+    /// Makes an offer for any Item of the collection, "amount" is the price paid for each Item and "quantity" the number
+    /// of Items the bidder wants to buy. The contract keeps "amount * quantity" until the bid is filled or cancelled
+    /// If the bidder already had a collection bid, the remaining amount is refunded and the bid is replaced
+    fn place_collection_bid(
+        env: Env,
+        bidder: Address,
+        amount: u128,
+        quantity: u32,
+        expiration_ledger: u32,
+    );
+
+    /// Cancels the collection bid and refunds the amount that hasn't been used yet
+    fn cancel_collection_bid(env: Env, bidder: Address);
+
+    /// The owner of a minted Item sells it to a collection bid, the royalties are paid the same way they are when
+    /// someone buys the Item and the quantity of the bid goes down by one
+    fn accept_collection_bid(env: Env, item_number: u64, bidder: Address);
+
+    /// Returns the collection bid of a bidder, if it doesn't exist it will throw an error
+    fn collection_bid(env: Env, bidder: Address) -> CollectionBid;
+
+    /// Starts an auction for an Item, the owner of the Item is used as the required authorization
+    /// While the auction is active the Item can't be sold, transferred or used to accept offers
+    fn start_auction(
+        env: Env,
+        item_number: u64,
+        reserve: u128,
+        min_increment_bps: u32,
+        end_ledger: u32,
+    );
+
+    /// Places a bid on an active auction, the amount is kept by the contract and the previous highest bid is added to
+    /// the refunds the previous bidder can withdraw with "withdraw_bid_refund"
+    /// If the bid is placed close to the end of the auction, the end ledger is extended
+    /// The seller of the Item can't bid on its own auction
+    fn bid(env: Env, bidder: Address, item_number: u64, amount: u128);
+
+    /// Sends to the bidder all the amounts it was outbid with, it returns the amount sent
+    fn withdraw_bid_refund(env: Env, bidder: Address) -> u128;
+
+    /// Returns the amount a bidder can withdraw with "withdraw_bid_refund"
+    fn bid_refund(env: Env, bidder: Address) -> u128;
+
+    /// Once the auction has ended anyone can settle it, the Item goes to the highest bidder and the royalties are paid
+    /// the same way they are when someone buys the Item
+    /// If nobody placed a bid, the auction is just removed
+    fn settle_auction(env: Env, item_number: u64);
+
+    /// Returns the current state of an auction, if it doesn't exist it will throw an error
+    fn auction(env: Env, item_number: u64) -> Auction;
+
+    /// Proposes to exchange Items with another account, the proposer must own "my_items" and the counterparty must own
+    /// "their_items". The proposer can add a payment which is kept by the contract until the swap is accepted or cancelled
+    /// It returns the id of the new swap
+    fn propose_swap(
+        env: Env,
+        proposer: Address,
+        my_items: Vec<u64>,
+        their_items: Vec<u64>,
+        counterparty: Address,
+        payment: Option<u128>,
+    ) -> u64;
+
+    /// The counterparty accepts the swap, all the Items change their ownership at the same time and the payment is sent
+    /// following the swap royalty policy
+    /// If any of the Items is no longer owned by the expected account it will throw an error
+    fn accept_swap(env: Env, swap_id: u64);
+
+    /// The proposer cancels the swap and the payment is refunded
+    fn cancel_swap(env: Env, swap_id: u64);
+
+    /// Returns a swap, if it doesn't exist it will throw an error
+    fn swap(env: Env, swap_id: u64) -> Swap;
+
+    /// Defines if the payments added to swaps pay royalties, only the admin can do this
+    fn set_swap_royalty_policy(env: Env, policy: SwapRoyaltyPolicy);
+
+    fn swap_royalty_policy(env: Env) -> SwapRoyaltyPolicy;
+
+    /// Lists a group of Items to be sold together for a single price, the seller must own all of them
+    /// While the bundle is active its Items can't be sold individually, transferred or used in any other operation
+    /// It returns the id of the new bundle
+    fn list_bundle(env: Env, seller: Address, items: Vec<u64>, price: u128) -> u64;
+
+    /// The seller cancels the bundle and its Items are released
+    fn cancel_bundle(env: Env, bundle_id: u64);
+
+    /// Buys all the Items of a bundle, the royalties are paid once over the total price of the bundle
+    /// If the price of the bundle is higher than "max_price" it will throw an error
+    fn buy_bundle(env: Env, buyer: Address, bundle_id: u64, max_price: u128);
+
+    /// Returns a bundle, if it doesn't exist it will throw an error
+    fn bundle(env: Env, bundle_id: u64) -> Bundle;
+
     fn decimals(e: Env) -> u32;
 
     fn name(e: Env) -> String;
 
     fn symbol(e: Env) -> String;
 
+    /// While the metadata hasn't been revealed this is a placeholder URI
     fn metadata_uri(e: Env) -> String;
 
+    /// Replaces the placeholder metadata URI with the final one, only the admin can do this
+    /// The final URI and the salt must match the hash committed at the initialization
+    fn reveal(e: Env, final_uri: String, salt: Bytes);
+
+    fn revealed(e: Env) -> bool;
+
     fn royalties(e: Env) -> Vec<Royalty>;
 
+    /// The royalties paid when the Item is sold, if the Item doesn't have its own royalties these are the royalties of
+    /// the collection. Bundles and swaps pay the collection royalties once over their total price, unless one of their
+    /// Items has its own royalties, in that case the price is split evenly between the Items and each Item pays its own
+    /// royalties over its share
+    fn item_royalties(e: Env, item_number: u64) -> Vec<Royalty>;
+
+    /// The royalties "buy" would pay if the Item is sold for "sale_price", one entry per royalty that applies
+    /// If the Item hasn't been minted the sale is its "first sale" so the primary percentages are used
+    /// Other marketplaces can use this to pay the same royalties this contract pays
+    fn royalty_info(e: Env, item_number: u64, sale_price: u128) -> Vec<(Address, u128)>;
+
+    /// Proposes new royalties for a specific Item, only the admin can do this and only while the royalties aren't
+    /// frozen. Like the collection royalties, they can be applied once the royalties delay has passed
+    /// Sending None proposes removing the override so the Item goes back to the royalties of the collection
+    fn propose_item_royalties(e: Env, item_number: u64, royalties: Option<Vec<Royalty>>);
+
+    /// Replaces the royalties of the Item with the proposed ones once the delay has passed, only the admin can do this
+    fn apply_item_royalties(e: Env, item_number: u64);
+
+    /// Returns the proposed royalties of the Item, if there isn't a proposal it will throw an error
+    fn pending_item_royalties(e: Env, item_number: u64) -> PendingItemRoyalties;
+
+    /// Proposes a new list of royalties, only the admin can do this
+    /// The new royalties can be applied once the royalties delay has passed, a new proposal replaces the previous one
+    fn propose_royalties(e: Env, royalties: Vec<Royalty>);
+
+    /// Replaces the royalties with the proposed ones once the delay has passed, only the admin can do this
+    fn apply_royalties(e: Env);
+
+    /// Makes the current royalties permanent, any pending proposal is discarded and the pending Item proposals can't be
+    /// applied anymore. Only the admin can do this
+    fn freeze_royalties(e: Env);
+
+    /// Sets the number of ledgers between proposing and applying new royalties, only the admin can do this
+    /// The delay can't be decreased so it can't be used to skip the timelock
+    fn set_royalties_delay(e: Env, ledgers: u32);
+
+    fn royalties_delay(e: Env) -> u32;
+
+    /// Returns the proposed royalties, if there isn't a proposal it will throw an error
+    fn pending_royalties(e: Env) -> PendingRoyalties;
+
+    fn royalties_frozen(e: Env) -> bool;
+
+    /// Converts the royalties of a collectible created before royalties had separate primary and secondary percentages,
+    /// only the admin can do this and it must be done right after upgrading the contract
+    /// Until then, every function that reads or writes the royalties fails with "RoyaltiesAreNotMigrated"
+    /// The royalties are converted without validating them, invalid legacy royalties can be replaced afterwards with
+    /// "propose_royalties"
+    fn migrate_royalties(e: Env);
+
     fn supply(e: Env) -> u64;
 
     fn core_data(e: Env) -> CoreData;
+
+    /// Sets a declining price schedule for the Items that haven't been minted yet, only the admin can do this
+    /// The price starts at "start_price" and goes down by "decay_per_ledger" on each ledger after "start_ledger" until it
+    /// reaches "floor_price"
+    /// The Dutch auction replaces the bonding curve if there is one
+    fn set_dutch_auction(
+        e: Env,
+        start_price: u128,
+        floor_price: u128,
+        start_ledger: u32,
+        decay_per_ledger: u128,
+    );
+
+    /// Removes the declining price schedule, the primary sale goes back to the initial price
+    fn remove_dutch_auction(e: Env);
+
+    /// Sets a price curve for the Items that haven't been minted yet, only the admin can do this
+    /// The price starts at "base_price" and goes up with each Item minted, by "increment" if the curve is linear or by
+    /// the "increment" percentage if it's exponential
+    /// The curve follows the minted count, so Items minted by the initial_seller or with free Vouchers also move the
+    /// price. If the price doesn't fit in a u128 the purchases fail with "BondingCurvePriceOverflow"
+    /// The bonding curve replaces the Dutch auction if there is one
+    fn set_bonding_curve(e: Env, kind: BondingCurveKind, base_price: u128, increment: u128);
+
+    /// Removes the price curve, the primary sale goes back to the initial price
+    fn remove_bonding_curve(e: Env);
+
+    /// The total price of the next "quantity" Items if they are bought following the price curve
+    fn curve_quote(e: Env, quantity: u64) -> u128;
+
+    /// The number of Items that have been minted, either bought in the primary sale, minted by the initial_seller or
+    /// redeemed with a Voucher
+    fn minted(e: Env) -> u64;
+
+    /// Contracts initialized before the minted count existed start counting from zero, the admin can set the number
+    /// of Items minted before the upgrade with this function. It must be called before any other Item is minted
+    fn seed_minted_count(e: Env, count: u64);
+
+    /// Items minted before the upgrade are still in the list "buy_random" picks from, the admin can remove them in
+    /// batches with this function so random purchases don't have to skip them. Items that are not minted are ignored
+    fn prune_unminted_items(e: Env, item_numbers: Vec<u64>);
+
+    /// The price the Item would have right now if it hasn't been minted yet
+    fn primary_price(e: Env, item_number: u64) -> u128;
+
+    /// Replaces the price tiers of the primary sale, only the admin can do this
+    /// Each tier is a range of item numbers with its own price, the tiers must be sorted and they can't overlap
+    /// While a sale phase is active its price is used instead of the tiers
+    /// Sending an empty list removes the tiers
+    fn set_price_tiers(e: Env, tiers: Vec<PriceTier>);
+
+    fn price_tiers(e: Env) -> Vec<PriceTier>;
+
+    /// Sets the primary price of a specific Item, it takes precedence over the sale phases and the price tiers, only the
+    /// admin can do this
+    /// Sending None removes the override
+    fn set_price_override(e: Env, item_number: u64, price: Option<u128>);
+
+    /// Sets the ledgers between which Items that haven't been minted can be bought (both included), only the admin can
+    /// do this. Secondary sales are not affected by this window
+    fn set_primary_sale_window(e: Env, start_ledger: u32, end_ledger: u32);
+
+    /// Stops the primary sale until "resume_primary_sale" is called, only the admin can do this
+    fn pause_primary_sale(e: Env);
+
+    fn resume_primary_sale(e: Env);
+
+    /// Returns the primary sale window and whether it's paused
+    fn primary_sale(e: Env) -> PrimarySale;
+
+    /// Replaces the phases of the primary sale, only the admin can do this
+    /// Each phase has its own start ledger, price and allowlist and they must be sorted by their start ledger
+    /// The price of the active phase takes precedence over the price tiers
+    /// Sending an empty list removes the phases
+    fn set_sale_phases(e: Env, phases: Vec<SalePhase>);
+
+    fn sale_phases(e: Env) -> Vec<SalePhase>;
+
+    /// Sets the maximum number of Items an Address can buy in the primary sale, only the admin can do this
+    /// Sale phases with their own limit override this value, sending None removes the limit
+    fn set_max_per_wallet(e: Env, max_per_wallet: Option<u32>);
+
+    fn max_per_wallet(e: Env) -> Option<u32>;
+
+    /// The number of Items the Address has bought in the primary sale
+    fn primary_purchases(e: Env, address: Address) -> u32;
+
     fn token_metadata(e: Env) -> TokenMetadata;
 }
 
@@ -95,10 +479,9 @@ impl CollectibleTrait for CollectibleContract {
         initial_price: u128,
         initial_seller: Address,
         collection_currency: Address,
-        name: String,
-        symbol: String,
-        metadata_uri: String,
+        token_metadata: TokenMetadata,
         royalties: Vec<Royalty>,
+        metadata_commitment: Option<BytesN<32>>,
     ) {
         if is_initialized(&env) {
             panic_with_error!(&env, &SCErrors::AlreadyInitialized);
@@ -115,16 +498,14 @@ impl CollectibleTrait for CollectibleContract {
             },
         );
 
-        write_token_metadata(
-            &env,
-            TokenMetadata {
-                name,
-                symbol,
-                metadata_uri,
-            },
-        );
+        write_token_metadata(&env, token_metadata);
+        if let Some(metadata_commitment) = metadata_commitment {
+            write_metadata_commitment(&env, &metadata_commitment);
+        }
 
+        write_royalties_version(&env, &ROYALTIES_VERSION);
         write_royalties(&env, &royalties);
+        write_minted_count(&env, &0);
 
         bump_instance(&env);
         bump_royalties(&env);
@@ -147,91 +528,232 @@ impl CollectibleTrait for CollectibleContract {
         get_balance(&env, &id)
     }
 
-    // NOTE: Something we can improve is the use of "is_minted" and "get_item" so we don't call the storage that often
-    fn buy(env: Env, buyer: Address, item_number: u64) {
+    fn buy(
+        env: Env,
+        buyer: Address,
+        item_number: u64,
+        max_price: u128,
+        proof: Option<Vec<BytesN<32>>>,
+    ) {
         bump_instance(&env);
         buyer.require_auth();
 
         let core_data: CoreData = get_core_data(&env);
+        let royalties: Vec<Royalty> = get_royalties(&env);
+        let mut payouts: Map<Address, u128> = Map::new(&env);
 
-        if !is_valid_item_number(&core_data, &item_number) {
-            panic_with_error!(&env, &SCErrors::ItemNumberIsInvalid);
-        }
+        let price: u128 = purchase_item(
+            &env,
+            &core_data,
+            &royalties,
+            &mut payouts,
+            &buyer,
+            &item_number,
+            &proof,
+        );
 
-        if !is_item_for_sale(&env, &item_number) {
-            panic_with_error!(&env, &SCErrors::ItemIsNotForSale);
+        if price > max_price {
+            panic_with_error!(&env, &SCErrors::PriceIsHigherThanMaxPrice);
         }
 
-        let is_minted_val: bool = is_minted(&env, &item_number);
+        send_payouts(&collection_currency(&env, &core_data), &buyer, &payouts);
+
+        bump_balance(&env, &buyer);
+        bump_royalties(&env);
+    }
+
+    fn buy_many(
+        env: Env,
+        buyer: Address,
+        item_numbers: Vec<u64>,
+        max_total: u128,
+        proof: Option<Vec<BytesN<32>>>,
+    ) {
+        bump_instance(&env);
+        buyer.require_auth();
 
-        // If is already minted, we reduce the balance amount of the old owner
-        if is_minted_val {
-            let item: Item = get_item(&env, &item_number);
-            let balance: u128 = get_balance(&env, &item.owner);
-            write_balance(&env, &item.owner, &(balance - 1));
-            bump_balance(&env, &item.owner);
+        if item_numbers.is_empty() {
+            panic_with_error!(&env, &SCErrors::ItemListIsEmpty);
         }
 
+        let core_data: CoreData = get_core_data(&env);
         let royalties: Vec<Royalty> = get_royalties(&env);
+        let mut payouts: Map<Address, u128> = Map::new(&env);
+
+        let mut total: u128 = 0;
+        for item_number in item_numbers.iter() {
+            total += purchase_item(
+                &env,
+                &core_data,
+                &royalties,
+                &mut payouts,
+                &buyer,
+                &item_number,
+                &proof,
+            );
+        }
 
-        let collection_currency = collection_currency(&env, &core_data);
-        let price: u128 = if is_minted_val {
-            get_item(&env, &item_number).price
-        } else {
-            core_data.initial_price
-        };
-        let seller: Address = if is_minted_val {
-            get_item(&env, &item_number).owner
-        } else {
-            core_data.initial_seller
-        };
+        if total > max_total {
+            panic_with_error!(&env, &SCErrors::PriceIsHigherThanMaxPrice);
+        }
 
-        // We distribute the royalties and we pay the owner
-        let mut royalties_distributed: u128 = 0u128;
-        for royalty in royalties.iter() {
-            if is_minted_val && royalty.first_sale {
-                // If is already minted, it means this is not a first sale so we ignore this distribution for second sales
-                continue;
-            }
+        send_payouts(&collection_currency(&env, &core_data), &buyer, &payouts);
 
-            let share = div_floor(price * royalty.percentage, 1_0000000);
-            collection_currency.transfer(&buyer, &royalty.address, &(share as i128));
-            royalties_distributed += share;
+        bump_balance(&env, &buyer);
+        bump_royalties(&env);
+    }
 
-            events::royalty_payment(&env, item_number.clone(), royalty.address, share);
-        }
+    fn buy_random(
+        env: Env,
+        buyer: Address,
+        max_price: u128,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> u64 {
+        bump_instance(&env);
+        buyer.require_auth();
 
-        // We set the new owner and increase its balance
-        write_item(
+        let item_number: u64 = pick_random_unminted_item(&env);
+
+        let core_data: CoreData = get_core_data(&env);
+        let royalties: Vec<Royalty> = get_royalties(&env);
+        let mut payouts: Map<Address, u128> = Map::new(&env);
+
+        let price: u128 = purchase_item(
             &env,
-            &Item {
-                number: item_number.clone(),
-                for_sale: false,
-                owner: buyer.clone(),
-                price: 0,
-            },
+            &core_data,
+            &royalties,
+            &mut payouts,
+            &buyer,
+            &item_number,
+            &proof,
         );
-        let mut new_owner_balance: u128 = get_balance(&env, &buyer);
-        new_owner_balance += 1;
-        write_balance(&env, &buyer, &new_owner_balance);
 
-        collection_currency.transfer(&buyer, &seller, &((price - royalties_distributed) as i128));
+        if price > max_price {
+            panic_with_error!(&env, &SCErrors::PriceIsHigherThanMaxPrice);
+        }
+
+        send_payouts(&collection_currency(&env, &core_data), &buyer, &payouts);
 
-        bump_item(&env, &item_number);
         bump_balance(&env, &buyer);
         bump_royalties(&env);
 
-        events::buy(&env, seller, buyer, item_number, price);
+        item_number
+    }
+
+    fn set_refund_window(env: Env, ledgers: u32) {
+        bump_instance(&env);
+        get_core_data(&env).admin.require_auth();
+        write_refund_window(&env, &ledgers);
+    }
+
+    fn refund(env: Env, buyer: Address, item_number: u64) {
+        bump_instance(&env);
+        buyer.require_auth();
+
+        let escrowed_sale: EscrowedSale = get_escrowed_sale(&env, &item_number);
+        let item: Item = get_item(&env, &item_number);
+
+        if escrowed_sale.buyer != buyer || item.owner != buyer {
+            panic_with_error!(&env, &SCErrors::ItemIsNotOwned);
+        }
+
+        if env.ledger().sequence() > escrowed_sale.refund_deadline {
+            panic_with_error!(&env, &SCErrors::RefundWindowHasClosed);
+        }
+
+        check_item_is_not_locked(&env, &item_number);
+
+        unmint_item(&env, &item);
+        remove_escrowed_sale(&env, &item_number);
+
+        collection_currency(&env, &get_core_data(&env)).transfer(
+            &env.current_contract_address(),
+            &buyer,
+            &(escrowed_sale.price as i128),
+        );
+
+        events::refund(&env, buyer, item_number, escrowed_sale.price);
+    }
+
+    fn release_proceeds(env: Env, item_numbers: Vec<u64>) {
+        bump_instance(&env);
+
+        if item_numbers.is_empty() {
+            panic_with_error!(&env, &SCErrors::ItemListIsEmpty);
+        }
+
+        let core_data: CoreData = get_core_data(&env);
+        let mut payouts: Map<Address, u128> = Map::new(&env);
+
+        for item_number in item_numbers.iter() {
+            let escrowed_sale: EscrowedSale = get_escrowed_sale(&env, &item_number);
+            if env.ledger().sequence() <= escrowed_sale.refund_deadline {
+                panic_with_error!(&env, &SCErrors::RefundWindowIsOpen);
+            }
+
+            collect_sale_payouts(
+                &env,
+                &escrowed_sale.royalties,
+                &mut payouts,
+                &core_data.initial_seller,
+                &item_number,
+                &escrowed_sale.price,
+                true,
+            );
+            remove_escrowed_sale(&env, &item_number);
+        }
+
+        send_payouts(
+            &collection_currency(&env, &core_data),
+            &env.current_contract_address(),
+            &payouts,
+        );
+
+        bump_royalties(&env);
+    }
+
+    fn escrowed_sale(env: Env, item_number: u64) -> EscrowedSale {
+        bump_instance(&env);
+        bump_escrowed_sale(&env, &item_number);
+        get_escrowed_sale(&env, &item_number)
     }
 
-    fn sell(env: Env, item_number: u64, price: u128) {
+    fn sell(
+        env: Env,
+        item_number: u64,
+        price: u128,
+        expiration_ledger: Option<u32>,
+        reserved_for: Option<Address>,
+    ) {
         bump_instance(&env);
 
         let mut item: Item = get_item(&env, &item_number);
         item.owner.require_auth();
+        check_item_is_not_locked(&env, &item_number);
+
+        if let Some(expiration) = expiration_ledger {
+            if expiration <= env.ledger().sequence() {
+                panic_with_error!(&env, &SCErrors::InvalidExpirationLedger);
+            }
+        }
 
-        item.for_sale = price != 0;
-        item.price = price;
+        if price == 0 {
+            remove_listing(&env, &mut item);
+        } else {
+            item.for_sale = true;
+            item.price = price;
+            write_listing(
+                &env,
+                &item_number,
+                &Listing {
+                    expiration_ledger,
+                    reserved_for: match reserved_for {
+                        Some(buyer) => Reservation::Buyer(buyer),
+                        None => Reservation::None,
+                    },
+                },
+            );
+        }
 
         write_item(&env, &item);
 
@@ -251,7 +773,15 @@ impl CollectibleTrait for CollectibleContract {
         }
 
         bump_item(&env, &number);
-        get_item(&env, &number)
+        let mut item: Item = get_item(&env, &number);
+        item.for_sale = is_item_for_sale(&env, &number);
+        item
+    }
+
+    fn listing(env: Env, number: u64) -> Listing {
+        bump_instance(&env);
+        bump_item(&env, &number);
+        get_listing(&env, &number)
     }
 
     fn transfer(env: Env, item_number: u64, to: Address) {
@@ -259,6 +789,7 @@ impl CollectibleTrait for CollectibleContract {
 
         let mut item: Item = get_item(&env, &item_number);
         item.owner.require_auth();
+        check_item_is_not_locked(&env, &item_number);
 
         // We first reduce the balance of the current owner
         let current_owner_balance = get_balance(&env, &item.owner);
@@ -270,8 +801,7 @@ impl CollectibleTrait for CollectibleContract {
 
         // We update the ownership of the item
         item.owner = to.clone();
-        item.price = 0;
-        item.for_sale = false;
+        remove_listing(&env, &mut item);
         write_item(&env, &item);
 
         bump_item(&env, &item_number);
@@ -291,20 +821,8 @@ impl CollectibleTrait for CollectibleContract {
             panic_with_error!(&e, &SCErrors::ItemWasAlreadyMinted);
         }
 
-        // We now increase new owner balance
-        let new_owner_balance: u128 = get_balance(&e, &to);
-        write_balance(&e, &to, &(new_owner_balance + 1));
-
         // We set the new owner and increase its balance
-        write_item(
-            &e,
-            &Item {
-                number: item_number.clone(),
-                for_sale: false,
-                owner: to.clone(),
-                price: 0,
-            },
-        );
+        mint_item(&e, &item_number, &to);
 
         bump_item(&e, &item_number);
         bump_balance(&e, &to);
@@ -313,43 +831,1160 @@ impl CollectibleTrait for CollectibleContract {
         events::mint(&e, to, item_number);
     }
 
-    fn decimals(e: Env) -> u32 {
-        bump_instance(&e);
-        0
+    fn set_voucher_signer(env: Env, public_key: BytesN<32>) {
+        bump_instance(&env);
+        get_core_data(&env).initial_seller.require_auth();
+        write_voucher_signer(&env, &public_key);
     }
 
-    fn name(e: Env) -> String {
-        bump_instance(&e);
-        get_metadata(&e).name
-    }
+    fn redeem_voucher(env: Env, recipient: Address, voucher: Voucher, signature: BytesN<64>) {
+        bump_instance(&env);
+        recipient.require_auth();
 
-    fn symbol(e: Env) -> String {
-        bump_instance(&e);
-        get_metadata(&e).symbol
-    }
+        let core_data: CoreData = get_core_data(&env);
 
-    fn metadata_uri(e: Env) -> String {
-        bump_instance(&e);
-        get_metadata(&e).metadata_uri
-    }
+        if voucher.recipient != recipient {
+            panic_with_error!(&env, &SCErrors::InvalidVoucher);
+        }
 
-    fn royalties(e: Env) -> Vec<Royalty> {
-        bump_instance(&e);
-        get_royalties(&e)
+        if env.ledger().sequence() > voucher.expiration_ledger {
+            panic_with_error!(&env, &SCErrors::VoucherHasExpired);
+        }
+
+        if is_nonce_used(&env, &voucher.nonce) {
+            panic_with_error!(&env, &SCErrors::VoucherWasAlreadyUsed);
+        }
+
+        if !is_valid_item_number(&core_data, &voucher.item_number) {
+            panic_with_error!(&env, &SCErrors::ItemNumberIsInvalid);
+        }
+
+        if is_minted(&env, &voucher.item_number) {
+            panic_with_error!(&env, &SCErrors::ItemWasAlreadyMinted);
+        }
+
+        let message: Bytes = (env.current_contract_address(), voucher.clone()).to_xdr(&env);
+        env.crypto()
+            .ed25519_verify(&get_voucher_signer(&env), &message, &signature);
+
+        write_used_nonce(&env, &voucher.nonce);
+        mint_item(&env, &voucher.item_number, &recipient);
+
+        if voucher.price > 0 {
+            let mut payouts: Map<Address, u128> = Map::new(&env);
+            collect_primary_payouts(
+                &env,
+                &get_item_royalties(&env, &voucher.item_number),
+                &mut payouts,
+                &core_data.initial_seller,
+                &recipient,
+                &voucher.item_number,
+                &voucher.price,
+            );
+            send_payouts(&collection_currency(&env, &core_data), &recipient, &payouts);
+            bump_royalties(&env);
+        }
+
+        bump_item(&env, &voucher.item_number);
+        bump_balance(&env, &recipient);
+
+        events::mint(&env, recipient, voucher.item_number);
     }
 
-    fn supply(e: Env) -> u64 {
-        bump_instance(&e);
-        get_core_data(&e).supply
+    fn set_reserved_items(env: Env, caller: Address, reserved_items: Vec<ReservedItems>) {
+        bump_instance(&env);
+        let core_data: CoreData = get_core_data(&env);
+
+        if caller != core_data.admin && caller != core_data.initial_seller {
+            panic_with_error!(&env, &SCErrors::UnauthorizedCaller);
+        }
+        caller.require_auth();
+
+        for range in reserved_items.iter() {
+            if range.from > range.to || !is_valid_item_number(&core_data, &range.to) {
+                panic_with_error!(&env, &SCErrors::InvalidReservedItems);
+            }
+        }
+
+        write_reserved_items(&env, &reserved_items);
     }
 
-    fn core_data(e: Env) -> CoreData {
-        bump_instance(&e);
-        get_core_data(&e)
+    fn reserved_items(env: Env) -> Vec<ReservedItems> {
+        bump_instance(&env);
+        get_reserved_items(&env)
     }
 
-    fn token_metadata(e: Env) -> TokenMetadata {
-        bump_instance(&e);
-        get_metadata(&e)
+    fn make_offer(
+        env: Env,
+        bidder: Address,
+        item_number: u64,
+        amount: u128,
+        expiration_ledger: u32,
+    ) {
+        bump_instance(&env);
+        bidder.require_auth();
+
+        if !is_minted(&env, &item_number) {
+            panic_with_error!(&env, &SCErrors::ItemHasNotBeenMinted);
+        }
+
+        if amount == 0 {
+            panic_with_error!(&env, &SCErrors::InvalidAmount);
+        }
+
+        if expiration_ledger <= env.ledger().sequence() {
+            panic_with_error!(&env, &SCErrors::InvalidExpirationLedger);
+        }
+
+        let core_data: CoreData = get_core_data(&env);
+        let collection_currency = collection_currency(&env, &core_data);
+
+        // If there was a previous offer, we refund it before escrowing the new amount
+        if has_offer(&env, &item_number, &bidder) {
+            let previous_offer: Offer = get_offer(&env, &item_number, &bidder);
+            collection_currency.transfer(
+                &env.current_contract_address(),
+                &bidder,
+                &(previous_offer.amount as i128),
+            );
+        }
+
+        collection_currency.transfer(&bidder, &env.current_contract_address(), &(amount as i128));
+
+        write_offer(
+            &env,
+            &Offer {
+                bidder: bidder.clone(),
+                item_number,
+                amount,
+                expiration_ledger,
+            },
+        );
+
+        bump_offer(&env, &item_number, &bidder);
+        bump_item(&env, &item_number);
+
+        events::offer(&env, bidder, item_number, amount, expiration_ledger);
+    }
+
+    fn cancel_offer(env: Env, bidder: Address, item_number: u64) {
+        bump_instance(&env);
+        bidder.require_auth();
+
+        let offer: Offer = get_offer(&env, &item_number, &bidder);
+        remove_offer(&env, &item_number, &bidder);
+
+        let core_data: CoreData = get_core_data(&env);
+        collection_currency(&env, &core_data).transfer(
+            &env.current_contract_address(),
+            &bidder,
+            &(offer.amount as i128),
+        );
+
+        events::offer_cancelled(&env, bidder, item_number, offer.amount);
+    }
+
+    fn accept_offer(env: Env, item_number: u64, bidder: Address) {
+        bump_instance(&env);
+
+        let mut item: Item = get_item(&env, &item_number);
+        item.owner.require_auth();
+        check_item_is_not_locked(&env, &item_number);
+
+        let offer: Offer = get_offer(&env, &item_number, &bidder);
+        if offer.expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, &SCErrors::OfferHasExpired);
+        }
+
+        remove_offer(&env, &item_number, &bidder);
+
+        // The offered amount is already in the contract so the payments are made from the contract itself
+        let seller: Address = item.owner.clone();
+        settle_escrowed_sale(&env, &mut item, &bidder, &offer.amount);
+
+        bump_item(&env, &item_number);
+        bump_royalties(&env);
+
+        events::offer_accepted(&env, seller, bidder, item_number, offer.amount);
+    }
+
+    fn offer(env: Env, item_number: u64, bidder: Address) -> Offer {
+        bump_instance(&env);
+        bump_offer(&env, &item_number, &bidder);
+        get_offer(&env, &item_number, &bidder)
+    }
+
+    fn decimals(e: Env) -> u32 {
+        bump_instance(&e);
+        0
+    }
+
+    fn name(e: Env) -> String {
+        bump_instance(&e);
+        get_metadata(&e).name
+    }
+
+    fn symbol(e: Env) -> String {
+        bump_instance(&e);
+        get_metadata(&e).symbol
+    }
+
+    fn metadata_uri(e: Env) -> String {
+        bump_instance(&e);
+        get_metadata(&e).metadata_uri
+    }
+
+    fn reveal(e: Env, final_uri: String, salt: Bytes) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        if is_metadata_revealed(&e) {
+            panic_with_error!(&e, &SCErrors::MetadataIsAlreadyRevealed);
+        }
+
+        let commitment: BytesN<32> = match get_metadata_commitment(&e) {
+            Some(commitment) => commitment,
+            None => panic_with_error!(&e, &SCErrors::MetadataIsNotCommitted),
+        };
+
+        if metadata_commitment_hash(&e, &final_uri, &salt) != commitment {
+            panic_with_error!(&e, &SCErrors::InvalidMetadataReveal);
+        }
+
+        let mut token_metadata: TokenMetadata = get_metadata(&e);
+        token_metadata.metadata_uri = final_uri.clone();
+        write_token_metadata(&e, token_metadata);
+        write_metadata_revealed(&e);
+
+        events::metadata_revealed(&e, final_uri);
+    }
+
+    fn revealed(e: Env) -> bool {
+        bump_instance(&e);
+        is_metadata_revealed(&e)
+    }
+
+    fn royalties(e: Env) -> Vec<Royalty> {
+        bump_instance(&e);
+        get_royalties(&e)
+    }
+
+    fn item_royalties(e: Env, item_number: u64) -> Vec<Royalty> {
+        bump_instance(&e);
+        get_item_royalties(&e, &item_number)
+    }
+
+    fn royalty_info(e: Env, item_number: u64, sale_price: u128) -> Vec<(Address, u128)> {
+        bump_instance(&e);
+
+        if !is_valid_item_number(&get_core_data(&e), &item_number) {
+            panic_with_error!(&e, &SCErrors::ItemNumberIsInvalid);
+        }
+
+        royalty_shares(
+            &e,
+            &get_item_royalties(&e, &item_number),
+            &sale_price,
+            !is_minted(&e, &item_number),
+        )
+    }
+
+    fn propose_item_royalties(e: Env, item_number: u64, royalties: Option<Vec<Royalty>>) {
+        bump_instance(&e);
+        let core_data: CoreData = get_core_data(&e);
+        core_data.admin.require_auth();
+        check_royalties_are_not_frozen(&e);
+
+        if !is_valid_item_number(&core_data, &item_number) {
+            panic_with_error!(&e, &SCErrors::ItemNumberIsInvalid);
+        }
+
+        if let Some(royalties) = &royalties {
+            validate_royalties(&e, royalties);
+        }
+
+        let apply_ledger: u32 = e
+            .ledger()
+            .sequence()
+            .saturating_add(get_royalties_delay(&e));
+        write_pending_item_royalties(
+            &e,
+            &item_number,
+            &PendingItemRoyalties {
+                update: match royalties.clone() {
+                    Some(royalties) => ItemRoyaltiesUpdate::Override(royalties),
+                    None => ItemRoyaltiesUpdate::RemoveOverride,
+                },
+                apply_ledger,
+            },
+        );
+        bump_pending_item_royalties(&e, &item_number);
+
+        events::item_royalties_proposed(&e, item_number, royalties, apply_ledger);
+    }
+
+    fn apply_item_royalties(e: Env, item_number: u64) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+        check_royalties_are_not_frozen(&e);
+
+        let pending_item_royalties: PendingItemRoyalties =
+            get_pending_item_royalties(&e, &item_number);
+        if e.ledger().sequence() < pending_item_royalties.apply_ledger {
+            panic_with_error!(&e, &SCRoyaltyErrors::RoyaltiesTimelockIsActive);
+        }
+
+        let royalties: Option<Vec<Royalty>> = match pending_item_royalties.update {
+            ItemRoyaltiesUpdate::Override(royalties) => Some(royalties),
+            ItemRoyaltiesUpdate::RemoveOverride => None,
+        };
+        write_item_royalties(&e, &item_number, &royalties);
+        remove_pending_item_royalties(&e, &item_number);
+        bump_item_royalties(&e, &item_number);
+
+        events::item_royalties(&e, item_number, royalties);
+    }
+
+    fn pending_item_royalties(e: Env, item_number: u64) -> PendingItemRoyalties {
+        bump_instance(&e);
+        bump_pending_item_royalties(&e, &item_number);
+        get_pending_item_royalties(&e, &item_number)
+    }
+
+    fn propose_royalties(e: Env, royalties: Vec<Royalty>) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+        check_royalties_are_not_frozen(&e);
+
+        validate_royalties(&e, &royalties);
+
+        let apply_ledger: u32 = e
+            .ledger()
+            .sequence()
+            .saturating_add(get_royalties_delay(&e));
+        write_pending_royalties(
+            &e,
+            &PendingRoyalties {
+                royalties: royalties.clone(),
+                apply_ledger,
+            },
+        );
+        bump_pending_royalties(&e);
+
+        events::royalties_proposed(&e, royalties, apply_ledger);
+    }
+
+    fn apply_royalties(e: Env) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+        check_royalties_are_not_frozen(&e);
+
+        let pending_royalties: PendingRoyalties = get_pending_royalties(&e);
+        if e.ledger().sequence() < pending_royalties.apply_ledger {
+            panic_with_error!(&e, &SCRoyaltyErrors::RoyaltiesTimelockIsActive);
+        }
+
+        write_royalties(&e, &pending_royalties.royalties);
+        remove_pending_royalties(&e);
+        bump_royalties(&e);
+
+        events::royalties_applied(&e, pending_royalties.royalties);
+    }
+
+    fn freeze_royalties(e: Env) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+        check_royalties_are_not_frozen(&e);
+
+        write_royalties_frozen(&e);
+        remove_pending_royalties(&e);
+
+        events::royalties_frozen(&e);
+    }
+
+    fn set_royalties_delay(e: Env, ledgers: u32) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        if ledgers < get_royalties_delay(&e) {
+            panic_with_error!(&e, &SCRoyaltyErrors::RoyaltiesDelayCantDecrease);
+        }
+
+        write_royalties_delay(&e, &ledgers);
+    }
+
+    fn royalties_delay(e: Env) -> u32 {
+        bump_instance(&e);
+        get_royalties_delay(&e)
+    }
+
+    fn pending_royalties(e: Env) -> PendingRoyalties {
+        bump_instance(&e);
+        bump_pending_royalties(&e);
+        get_pending_royalties(&e)
+    }
+
+    fn royalties_frozen(e: Env) -> bool {
+        bump_instance(&e);
+        is_royalties_frozen(&e)
+    }
+
+    fn migrate_royalties(e: Env) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        migrate_royalties(&e);
+        bump_royalties(&e);
+    }
+
+    fn supply(e: Env) -> u64 {
+        bump_instance(&e);
+        get_core_data(&e).supply
+    }
+
+    fn core_data(e: Env) -> CoreData {
+        bump_instance(&e);
+        get_core_data(&e)
+    }
+
+    fn token_metadata(e: Env) -> TokenMetadata {
+        bump_instance(&e);
+        get_metadata(&e)
+    }
+
+    fn set_dutch_auction(
+        e: Env,
+        start_price: u128,
+        floor_price: u128,
+        start_ledger: u32,
+        decay_per_ledger: u128,
+    ) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        if floor_price > start_price {
+            panic_with_error!(&e, &SCErrors::InvalidPriceSchedule);
+        }
+
+        write_dutch_auction(
+            &e,
+            &DutchAuction {
+                start_price,
+                floor_price,
+                start_ledger,
+                decay_per_ledger,
+            },
+        );
+        remove_bonding_curve(&e);
+    }
+
+    fn remove_dutch_auction(e: Env) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+        remove_dutch_auction(&e);
+    }
+
+    fn set_bonding_curve(e: Env, kind: BondingCurveKind, base_price: u128, increment: u128) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        write_bonding_curve(
+            &e,
+            &BondingCurve {
+                kind,
+                base_price,
+                increment,
+            },
+        );
+        remove_dutch_auction(&e);
+    }
+
+    fn remove_bonding_curve(e: Env) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+        remove_bonding_curve(&e);
+    }
+
+    fn curve_quote(e: Env, quantity: u64) -> u128 {
+        bump_instance(&e);
+
+        let bonding_curve: BondingCurve = match get_bonding_curve(&e) {
+            Some(bonding_curve) => bonding_curve,
+            None => panic_with_error!(&e, &SCErrors::BondingCurveDoesNotExist),
+        };
+
+        let minted: u64 = get_minted_count(&e);
+        let mut total: u128 = 0;
+        for sold in minted..(minted + quantity) {
+            total = match total.checked_add(bonding_curve_price(&e, &bonding_curve, sold)) {
+                Some(total) => total,
+                None => panic_with_error!(&e, &SCErrors::BondingCurvePriceOverflow),
+            };
+        }
+
+        total
+    }
+
+    fn minted(e: Env) -> u64 {
+        bump_instance(&e);
+        get_minted_count(&e)
+    }
+
+    fn seed_minted_count(e: Env, count: u64) {
+        bump_instance(&e);
+        let core_data: CoreData = get_core_data(&e);
+        core_data.admin.require_auth();
+
+        if has_minted_count(&e) {
+            panic_with_error!(&e, &SCErrors::AlreadyInitialized);
+        }
+
+        if count > core_data.supply + 1 {
+            panic_with_error!(&e, &SCErrors::InvalidAmount);
+        }
+
+        write_minted_count(&e, &count);
+    }
+
+    fn prune_unminted_items(e: Env, item_numbers: Vec<u64>) {
+        bump_instance(&e);
+        let core_data: CoreData = get_core_data(&e);
+        core_data.admin.require_auth();
+
+        for item_number in item_numbers.iter() {
+            if is_minted(&e, &item_number) {
+                remove_unminted_item(&e, &item_number);
+            }
+        }
+    }
+
+    fn primary_price(e: Env, item_number: u64) -> u128 {
+        bump_instance(&e);
+        let core_data: CoreData = get_core_data(&e);
+
+        if !is_valid_item_number(&core_data, &item_number) {
+            panic_with_error!(&e, &SCErrors::ItemNumberIsInvalid);
+        }
+
+        get_primary_price(&e, &core_data, &item_number)
+    }
+
+    fn set_price_tiers(e: Env, tiers: Vec<PriceTier>) {
+        bump_instance(&e);
+        let core_data: CoreData = get_core_data(&e);
+        core_data.admin.require_auth();
+
+        let mut previous_to: Option<u64> = None;
+        for tier in tiers.iter() {
+            if tier.from > tier.to || !is_valid_item_number(&core_data, &tier.to) {
+                panic_with_error!(&e, &SCErrors::InvalidPriceTiers);
+            }
+
+            if let Some(previous) = previous_to {
+                if tier.from <= previous {
+                    panic_with_error!(&e, &SCErrors::InvalidPriceTiers);
+                }
+            }
+            previous_to = Some(tier.to);
+        }
+
+        write_price_tiers(&e, &tiers);
+    }
+
+    fn price_tiers(e: Env) -> Vec<PriceTier> {
+        bump_instance(&e);
+        get_price_tiers(&e)
+    }
+
+    fn set_price_override(e: Env, item_number: u64, price: Option<u128>) {
+        bump_instance(&e);
+        let core_data: CoreData = get_core_data(&e);
+        core_data.admin.require_auth();
+
+        if !is_valid_item_number(&core_data, &item_number) {
+            panic_with_error!(&e, &SCErrors::ItemNumberIsInvalid);
+        }
+
+        write_price_override(&e, &item_number, &price);
+        bump_price_override(&e, &item_number);
+    }
+
+    fn set_primary_sale_window(e: Env, start_ledger: u32, end_ledger: u32) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        if start_ledger > end_ledger {
+            panic_with_error!(&e, &SCErrors::InvalidSaleWindow);
+        }
+
+        let mut primary_sale: PrimarySale = get_primary_sale(&e);
+        primary_sale.start_ledger = start_ledger;
+        primary_sale.end_ledger = end_ledger;
+        write_primary_sale(&e, &primary_sale);
+    }
+
+    fn pause_primary_sale(e: Env) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        let mut primary_sale: PrimarySale = get_primary_sale(&e);
+        primary_sale.paused = true;
+        write_primary_sale(&e, &primary_sale);
+    }
+
+    fn resume_primary_sale(e: Env) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        let mut primary_sale: PrimarySale = get_primary_sale(&e);
+        primary_sale.paused = false;
+        write_primary_sale(&e, &primary_sale);
+    }
+
+    fn primary_sale(e: Env) -> PrimarySale {
+        bump_instance(&e);
+        get_primary_sale(&e)
+    }
+
+    fn set_sale_phases(e: Env, phases: Vec<SalePhase>) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+
+        let mut previous_start: Option<u32> = None;
+        for phase in phases.iter() {
+            if let Some(previous) = previous_start {
+                if phase.start_ledger <= previous {
+                    panic_with_error!(&e, &SCErrors::InvalidSalePhases);
+                }
+            }
+            previous_start = Some(phase.start_ledger);
+        }
+
+        write_sale_phases(&e, &phases);
+    }
+
+    fn sale_phases(e: Env) -> Vec<SalePhase> {
+        bump_instance(&e);
+        get_sale_phases(&e)
+    }
+
+    fn set_max_per_wallet(e: Env, max_per_wallet: Option<u32>) {
+        bump_instance(&e);
+        get_core_data(&e).admin.require_auth();
+        write_max_per_wallet(&e, &max_per_wallet);
+    }
+
+    fn max_per_wallet(e: Env) -> Option<u32> {
+        bump_instance(&e);
+        get_max_per_wallet(&e)
+    }
+
+    fn primary_purchases(e: Env, address: Address) -> u32 {
+        bump_instance(&e);
+        bump_primary_purchases(&e, &address);
+        get_primary_purchases(&e, &address)
+    }
+
+    fn place_collection_bid(
+        env: Env,
+        bidder: Address,
+        amount: u128,
+        quantity: u32,
+        expiration_ledger: u32,
+    ) {
+        bump_instance(&env);
+        bidder.require_auth();
+
+        if amount == 0 || quantity == 0 {
+            panic_with_error!(&env, &SCErrors::InvalidAmount);
+        }
+
+        if expiration_ledger <= env.ledger().sequence() {
+            panic_with_error!(&env, &SCErrors::InvalidExpirationLedger);
+        }
+
+        let core_data: CoreData = get_core_data(&env);
+        let collection_currency = collection_currency(&env, &core_data);
+
+        // If there was a previous bid, we refund what is left of it before escrowing the new amount
+        if has_collection_bid(&env, &bidder) {
+            let previous_bid: CollectionBid = get_collection_bid(&env, &bidder);
+            collection_currency.transfer(
+                &env.current_contract_address(),
+                &bidder,
+                &((previous_bid.amount * previous_bid.quantity as u128) as i128),
+            );
+        }
+
+        collection_currency.transfer(
+            &bidder,
+            &env.current_contract_address(),
+            &((amount * quantity as u128) as i128),
+        );
+
+        write_collection_bid(
+            &env,
+            &CollectionBid {
+                bidder: bidder.clone(),
+                amount,
+                quantity,
+                expiration_ledger,
+            },
+        );
+
+        bump_collection_bid(&env, &bidder);
+
+        events::collection_bid(&env, bidder, amount, quantity, expiration_ledger);
+    }
+
+    fn cancel_collection_bid(env: Env, bidder: Address) {
+        bump_instance(&env);
+        bidder.require_auth();
+
+        let collection_bid: CollectionBid = get_collection_bid(&env, &bidder);
+        remove_collection_bid(&env, &bidder);
+
+        let core_data: CoreData = get_core_data(&env);
+        collection_currency(&env, &core_data).transfer(
+            &env.current_contract_address(),
+            &bidder,
+            &((collection_bid.amount * collection_bid.quantity as u128) as i128),
+        );
+
+        events::collection_bid_cancelled(
+            &env,
+            bidder,
+            collection_bid.amount,
+            collection_bid.quantity,
+        );
+    }
+
+    fn accept_collection_bid(env: Env, item_number: u64, bidder: Address) {
+        bump_instance(&env);
+
+        let mut item: Item = get_item(&env, &item_number);
+        item.owner.require_auth();
+        check_item_is_not_locked(&env, &item_number);
+
+        let mut collection_bid: CollectionBid = get_collection_bid(&env, &bidder);
+        if collection_bid.expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, &SCErrors::OfferHasExpired);
+        }
+
+        collection_bid.quantity -= 1;
+        if collection_bid.quantity == 0 {
+            remove_collection_bid(&env, &bidder);
+        } else {
+            write_collection_bid(&env, &collection_bid);
+            bump_collection_bid(&env, &bidder);
+        }
+
+        // The bid amount is already in the contract so the payments are made from the contract itself
+        let seller: Address = item.owner.clone();
+        settle_escrowed_sale(&env, &mut item, &bidder, &collection_bid.amount);
+
+        bump_item(&env, &item_number);
+        bump_royalties(&env);
+
+        events::collection_bid_filled(&env, seller, bidder, item_number, collection_bid.amount);
+    }
+
+    fn collection_bid(env: Env, bidder: Address) -> CollectionBid {
+        bump_instance(&env);
+        bump_collection_bid(&env, &bidder);
+        get_collection_bid(&env, &bidder)
+    }
+
+    fn start_auction(
+        env: Env,
+        item_number: u64,
+        reserve: u128,
+        min_increment_bps: u32,
+        end_ledger: u32,
+    ) {
+        bump_instance(&env);
+
+        let mut item: Item = get_item(&env, &item_number);
+        item.owner.require_auth();
+        check_item_is_not_locked(&env, &item_number);
+
+        if end_ledger <= env.ledger().sequence() {
+            panic_with_error!(&env, &SCErrors::InvalidEndLedger);
+        }
+
+        // An Item in auction can't be bought directly so we remove the current sale offer
+        remove_listing(&env, &mut item);
+        write_item(&env, &item);
+
+        write_auction(
+            &env,
+            &Auction {
+                item_number,
+                seller: item.owner.clone(),
+                reserve,
+                min_increment_bps,
+                end_ledger,
+                highest_bidder: item.owner.clone(),
+                highest_bid: 0,
+            },
+        );
+
+        bump_item(&env, &item_number);
+        bump_auction(&env, &item_number);
+
+        events::auction_started(&env, item.owner, item_number, reserve, end_ledger);
+    }
+
+    fn bid(env: Env, bidder: Address, item_number: u64, amount: u128) {
+        bump_instance(&env);
+        bidder.require_auth();
+
+        let mut auction: Auction = get_auction(&env, &item_number);
+        let current_ledger: u32 = env.ledger().sequence();
+
+        if current_ledger > auction.end_ledger {
+            panic_with_error!(&env, &SCErrors::AuctionHasEnded);
+        }
+
+        if bidder == auction.seller {
+            panic_with_error!(&env, &SCErrors::BidderIsTheSeller);
+        }
+
+        if amount == 0 || amount < min_next_bid(&auction) {
+            panic_with_error!(&env, &SCErrors::BidIsTooLow);
+        }
+
+        let core_data: CoreData = get_core_data(&env);
+        let collection_currency = collection_currency(&env, &core_data);
+
+        collection_currency.transfer(&bidder, &env.current_contract_address(), &(amount as i128));
+
+        // The previous bid is not sent back here, otherwise a bidder that can't receive the currency would block the
+        // auction
+        if has_bids(&auction) {
+            let refund: u128 = get_bid_refund(&env, &auction.highest_bidder);
+            write_bid_refund(
+                &env,
+                &auction.highest_bidder,
+                &(refund + auction.highest_bid),
+            );
+            bump_bid_refund(&env, &auction.highest_bidder);
+        }
+
+        auction.highest_bidder = bidder.clone();
+        auction.highest_bid = amount;
+
+        if auction.end_ledger - current_ledger < AUCTION_EXTENSION_LEDGERS {
+            auction.end_ledger = current_ledger + AUCTION_EXTENSION_LEDGERS;
+        }
+
+        write_auction(&env, &auction);
+        bump_auction(&env, &item_number);
+
+        events::auction_bid(&env, bidder, item_number, amount, auction.end_ledger);
+    }
+
+    fn withdraw_bid_refund(env: Env, bidder: Address) -> u128 {
+        bump_instance(&env);
+        bidder.require_auth();
+
+        let refund: u128 = get_bid_refund(&env, &bidder);
+
+        if refund > 0 {
+            remove_bid_refund(&env, &bidder);
+
+            let core_data: CoreData = get_core_data(&env);
+            collection_currency(&env, &core_data).transfer(
+                &env.current_contract_address(),
+                &bidder,
+                &(refund as i128),
+            );
+
+            events::bid_refund_withdrawn(&env, bidder, refund);
+        }
+
+        refund
+    }
+
+    fn bid_refund(env: Env, bidder: Address) -> u128 {
+        bump_instance(&env);
+        bump_bid_refund(&env, &bidder);
+        get_bid_refund(&env, &bidder)
+    }
+
+    fn settle_auction(env: Env, item_number: u64) {
+        bump_instance(&env);
+
+        let auction: Auction = get_auction(&env, &item_number);
+
+        if env.ledger().sequence() <= auction.end_ledger {
+            panic_with_error!(&env, &SCErrors::AuctionHasNotEnded);
+        }
+
+        remove_auction(&env, &item_number);
+
+        if has_bids(&auction) {
+            // The winning bid is already in the contract so the payments are made from the contract itself
+            let mut item: Item = get_item(&env, &item_number);
+            settle_escrowed_sale(
+                &env,
+                &mut item,
+                &auction.highest_bidder,
+                &auction.highest_bid,
+            );
+
+            bump_royalties(&env);
+        }
+
+        bump_item(&env, &item_number);
+
+        events::auction_settled(
+            &env,
+            auction.seller,
+            auction.highest_bidder,
+            item_number,
+            auction.highest_bid,
+        );
+    }
+
+    fn auction(env: Env, item_number: u64) -> Auction {
+        bump_instance(&env);
+        bump_auction(&env, &item_number);
+        get_auction(&env, &item_number)
+    }
+
+    fn propose_swap(
+        env: Env,
+        proposer: Address,
+        my_items: Vec<u64>,
+        their_items: Vec<u64>,
+        counterparty: Address,
+        payment: Option<u128>,
+    ) -> u64 {
+        bump_instance(&env);
+        proposer.require_auth();
+
+        if my_items.is_empty() || their_items.is_empty() {
+            panic_with_error!(&env, &SCErrors::ItemListIsEmpty);
+        }
+
+        if proposer == counterparty {
+            panic_with_error!(&env, &SCErrors::InvalidCounterparty);
+        }
+
+        // We check the ownership now so invalid swaps are rejected early, it's checked again once the swap is accepted
+        get_owned_items(&env, &my_items, &proposer);
+        get_owned_items(&env, &their_items, &counterparty);
+
+        let payment: u128 = payment.unwrap_or(0);
+        if payment > 0 {
+            let core_data: CoreData = get_core_data(&env);
+            collection_currency(&env, &core_data).transfer(
+                &proposer,
+                &env.current_contract_address(),
+                &(payment as i128),
+            );
+        }
+
+        let swap_id: u64 = next_swap_id(&env);
+        write_swap(
+            &env,
+            &Swap {
+                id: swap_id,
+                proposer: proposer.clone(),
+                counterparty: counterparty.clone(),
+                proposer_items: my_items,
+                counterparty_items: their_items,
+                payment,
+            },
+        );
+
+        bump_swap(&env, &swap_id);
+
+        events::swap_proposed(&env, proposer, counterparty, swap_id, payment);
+
+        swap_id
+    }
+
+    fn accept_swap(env: Env, swap_id: u64) {
+        bump_instance(&env);
+
+        let swap: Swap = get_swap(&env, &swap_id);
+        swap.counterparty.require_auth();
+
+        let proposer_items: Vec<Item> = get_owned_items(&env, &swap.proposer_items, &swap.proposer);
+        let counterparty_items: Vec<Item> =
+            get_owned_items(&env, &swap.counterparty_items, &swap.counterparty);
+
+        remove_swap(&env, &swap_id);
+
+        for mut item in proposer_items.iter() {
+            change_owner(&env, &mut item, &swap.counterparty);
+            bump_item(&env, &item.number);
+        }
+
+        for mut item in counterparty_items.iter() {
+            change_owner(&env, &mut item, &swap.proposer);
+            bump_item(&env, &item.number);
+        }
+
+        if swap.payment > 0 {
+            let core_data: CoreData = get_core_data(&env);
+            let mut payouts: Map<Address, u128> = Map::new(&env);
+
+            match get_swap_royalty_policy(&env) {
+                SwapRoyaltyPolicy::NoRoyalties => {
+                    payouts.set(swap.counterparty.clone(), swap.payment);
+                }
+                SwapRoyaltyPolicy::RoyaltiesOnPayment => {
+                    // The payment is treated as the price the proposer pays for the Items of the counterparty, the
+                    // royalties are paid the same way they are in bundles
+                    collect_split_sale_payouts(
+                        &env,
+                        &mut payouts,
+                        &swap.counterparty,
+                        &swap.counterparty_items,
+                        &swap.payment,
+                    );
+                    bump_royalties(&env);
+                }
+            }
+
+            send_payouts(
+                &collection_currency(&env, &core_data),
+                &env.current_contract_address(),
+                &payouts,
+            );
+        }
+
+        events::swap_accepted(&env, swap.proposer, swap.counterparty, swap_id);
+    }
+
+    fn cancel_swap(env: Env, swap_id: u64) {
+        bump_instance(&env);
+
+        let swap: Swap = get_swap(&env, &swap_id);
+        swap.proposer.require_auth();
+
+        remove_swap(&env, &swap_id);
+
+        if swap.payment > 0 {
+            let core_data: CoreData = get_core_data(&env);
+            collection_currency(&env, &core_data).transfer(
+                &env.current_contract_address(),
+                &swap.proposer,
+                &(swap.payment as i128),
+            );
+        }
+
+        events::swap_cancelled(&env, swap.proposer, swap_id);
+    }
+
+    fn swap(env: Env, swap_id: u64) -> Swap {
+        bump_instance(&env);
+        bump_swap(&env, &swap_id);
+        get_swap(&env, &swap_id)
+    }
+
+    fn set_swap_royalty_policy(env: Env, policy: SwapRoyaltyPolicy) {
+        bump_instance(&env);
+        get_core_data(&env).admin.require_auth();
+        write_swap_royalty_policy(&env, &policy);
+    }
+
+    fn swap_royalty_policy(env: Env) -> SwapRoyaltyPolicy {
+        bump_instance(&env);
+        get_swap_royalty_policy(&env)
+    }
+
+    fn list_bundle(env: Env, seller: Address, items: Vec<u64>, price: u128) -> u64 {
+        bump_instance(&env);
+        seller.require_auth();
+
+        if items.is_empty() {
+            panic_with_error!(&env, &SCErrors::ItemListIsEmpty);
+        }
+
+        if price == 0 {
+            panic_with_error!(&env, &SCErrors::InvalidAmount);
+        }
+
+        // Bundled Items can't be bought individually so we remove their current sale offers
+        for mut item in get_owned_items(&env, &items, &seller).iter() {
+            remove_listing(&env, &mut item);
+            write_item(&env, &item);
+            bump_item(&env, &item.number);
+        }
+
+        let bundle_id: u64 = next_bundle_id(&env);
+        write_bundle(
+            &env,
+            &Bundle {
+                id: bundle_id,
+                seller: seller.clone(),
+                items,
+                price,
+            },
+        );
+
+        bump_bundle(&env, &bundle_id);
+
+        events::bundle_listed(&env, seller, bundle_id, price);
+
+        bundle_id
+    }
+
+    fn cancel_bundle(env: Env, bundle_id: u64) {
+        bump_instance(&env);
+
+        let bundle: Bundle = get_bundle(&env, &bundle_id);
+        bundle.seller.require_auth();
+
+        remove_bundle(&env, &bundle);
+
+        events::bundle_cancelled(&env, bundle.seller, bundle_id);
+    }
+
+    fn buy_bundle(env: Env, buyer: Address, bundle_id: u64, max_price: u128) {
+        bump_instance(&env);
+        buyer.require_auth();
+
+        let bundle: Bundle = get_bundle(&env, &bundle_id);
+
+        if bundle.price > max_price {
+            panic_with_error!(&env, &SCErrors::PriceIsHigherThanMaxPrice);
+        }
+
+        // The Items need to be released before they can change their owner
+        remove_bundle(&env, &bundle);
+
+        let core_data: CoreData = get_core_data(&env);
+        let mut payouts: Map<Address, u128> = Map::new(&env);
+
+        // The royalties are paid once over the total price unless one of the Items has its own royalties
+        collect_split_sale_payouts(
+            &env,
+            &mut payouts,
+            &bundle.seller,
+            &bundle.items,
+            &bundle.price,
+        );
+
+        for mut item in get_owned_items(&env, &bundle.items, &bundle.seller).iter() {
+            change_owner(&env, &mut item, &buyer);
+            bump_item(&env, &item.number);
+        }
+
+        send_payouts(&collection_currency(&env, &core_data), &buyer, &payouts);
+
+        bump_royalties(&env);
+
+        events::bundle_bought(&env, bundle.seller, buyer, bundle_id, bundle.price);
+    }
+
+    fn bundle(env: Env, bundle_id: u64) -> Bundle {
+        bump_instance(&env);
+        bump_bundle(&env, &bundle_id);
+        get_bundle(&env, &bundle_id)
     }
 }
@@ -10,4 +10,67 @@ pub enum SCErrors {
     ItemNumberIsInvalid = 3,
     ItemIsNotForSale = 4,
     ItemWasAlreadyMinted = 5,
+    OfferDoesNotExist = 6,
+    OfferHasExpired = 7,
+    InvalidExpirationLedger = 8,
+    InvalidAmount = 9,
+    ItemIsInAuction = 10,
+    AuctionDoesNotExist = 11,
+    AuctionHasEnded = 12,
+    AuctionHasNotEnded = 13,
+    BidIsTooLow = 14,
+    InvalidEndLedger = 15,
+    InvalidPriceSchedule = 16,
+    ListingHasExpired = 17,
+    ItemIsReservedForAnotherBuyer = 18,
+    PriceIsHigherThanMaxPrice = 19,
+    ItemListIsEmpty = 20,
+    CollectionBidDoesNotExist = 21,
+    SwapDoesNotExist = 22,
+    ItemIsNotOwned = 23,
+    ItemIsDuplicated = 24,
+    InvalidCounterparty = 25,
+    BundleDoesNotExist = 26,
+    ItemIsInBundle = 27,
+    PrimarySaleIsNotOpen = 28,
+    InvalidSalePhases = 29,
+    AddressIsNotAllowed = 30,
+    WalletLimitReached = 31,
+    InvalidSaleWindow = 32,
+    InvalidPriceTiers = 33,
+    AllItemsAreMinted = 34,
+    InvalidReservedItems = 35,
+    UnauthorizedCaller = 36,
+    BondingCurveDoesNotExist = 37,
+    VoucherSignerIsNotDefined = 38,
+    VoucherHasExpired = 39,
+    VoucherWasAlreadyUsed = 40,
+    InvalidVoucher = 41,
+    MetadataIsNotCommitted = 42,
+    MetadataIsAlreadyRevealed = 43,
+    InvalidMetadataReveal = 44,
+    EscrowedSaleDoesNotExist = 45,
+    RefundWindowHasClosed = 46,
+    RefundWindowIsOpen = 47,
+    BidderIsTheSeller = 48,
+    BondingCurvePriceOverflow = 49,
+}
+
+/// The contract spec allows up to 50 cases per error enum, the errors related to the royalties configuration are
+/// defined here and they start at 100 so they don't overlap with the SCErrors
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SCRoyaltyErrors {
+    RoyaltiesAreHigherThanPrice = 100,
+    RoyaltyAddressIsDuplicated = 101,
+    RoyaltyPercentageIsZero = 102,
+    RoyaltyNameIsEmpty = 103,
+    TooManyRoyalties = 104,
+    RoyaltiesAreFrozen = 105,
+    PendingRoyaltiesDoNotExist = 106,
+    RoyaltiesTimelockIsActive = 107,
+    RoyaltiesDelayCantDecrease = 108,
+    RoyaltiesAreAlreadyMigrated = 109,
+    RoyaltiesAreNotMigrated = 110,
 }
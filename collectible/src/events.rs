@@ -1,4 +1,5 @@
-use soroban_sdk::{symbol_short, Address, Env};
+use crate::storage::royalties::Royalty;
+use soroban_sdk::{symbol_short, Address, Env, String, Vec};
 
 pub(crate) fn buy(env: &Env, seller: Address, buyer: Address, item_id: u64, price: u128) {
     let topics = (&symbol_short!("buy"), seller, buyer);
@@ -24,3 +25,173 @@ pub(crate) fn royalty_payment(env: &Env, item_id: u64, to: Address, amount: u128
     let topics = (symbol_short!("royalty"), to);
     env.events().publish(topics, (item_id, amount));
 }
+
+pub(crate) fn offer(
+    env: &Env,
+    bidder: Address,
+    item_id: u64,
+    amount: u128,
+    expiration_ledger: u32,
+) {
+    let topics = (symbol_short!("offer"), bidder);
+    env.events()
+        .publish(topics, (item_id, amount, expiration_ledger));
+}
+
+pub(crate) fn offer_cancelled(env: &Env, bidder: Address, item_id: u64, amount: u128) {
+    let topics = (symbol_short!("offer_cnl"), bidder);
+    env.events().publish(topics, (item_id, amount));
+}
+
+pub(crate) fn offer_accepted(
+    env: &Env,
+    seller: Address,
+    bidder: Address,
+    item_id: u64,
+    amount: u128,
+) {
+    let topics = (symbol_short!("offer_acc"), seller, bidder);
+    env.events().publish(topics, (item_id, amount));
+}
+
+pub(crate) fn auction_started(
+    env: &Env,
+    seller: Address,
+    item_id: u64,
+    reserve: u128,
+    end_ledger: u32,
+) {
+    let topics = (symbol_short!("auc_start"), seller);
+    env.events().publish(topics, (item_id, reserve, end_ledger));
+}
+
+pub(crate) fn auction_bid(env: &Env, bidder: Address, item_id: u64, amount: u128, end_ledger: u32) {
+    let topics = (symbol_short!("auc_bid"), bidder);
+    env.events().publish(topics, (item_id, amount, end_ledger));
+}
+
+pub(crate) fn bid_refund_withdrawn(env: &Env, bidder: Address, amount: u128) {
+    let topics = (symbol_short!("bid_rfnd"), bidder);
+    env.events().publish(topics, amount);
+}
+
+pub(crate) fn auction_settled(
+    env: &Env,
+    seller: Address,
+    winner: Address,
+    item_id: u64,
+    amount: u128,
+) {
+    let topics = (symbol_short!("auc_settl"), seller);
+    env.events().publish(topics, (item_id, winner, amount));
+}
+
+pub(crate) fn collection_bid(
+    env: &Env,
+    bidder: Address,
+    amount: u128,
+    quantity: u32,
+    expiration_ledger: u32,
+) {
+    let topics = (symbol_short!("col_bid"), bidder);
+    env.events()
+        .publish(topics, (amount, quantity, expiration_ledger));
+}
+
+pub(crate) fn collection_bid_cancelled(env: &Env, bidder: Address, amount: u128, quantity: u32) {
+    let topics = (symbol_short!("col_bid_c"), bidder);
+    env.events().publish(topics, (amount, quantity));
+}
+
+pub(crate) fn collection_bid_filled(
+    env: &Env,
+    seller: Address,
+    bidder: Address,
+    item_id: u64,
+    amount: u128,
+) {
+    let topics = (symbol_short!("col_bid_f"), seller, bidder);
+    env.events().publish(topics, (item_id, amount));
+}
+
+pub(crate) fn swap_proposed(
+    env: &Env,
+    proposer: Address,
+    counterparty: Address,
+    swap_id: u64,
+    payment: u128,
+) {
+    let topics = (symbol_short!("swap_prop"), proposer, counterparty);
+    env.events().publish(topics, (swap_id, payment));
+}
+
+pub(crate) fn swap_accepted(env: &Env, proposer: Address, counterparty: Address, swap_id: u64) {
+    let topics = (symbol_short!("swap_acc"), proposer, counterparty);
+    env.events().publish(topics, swap_id);
+}
+
+pub(crate) fn swap_cancelled(env: &Env, proposer: Address, swap_id: u64) {
+    let topics = (symbol_short!("swap_cncl"), proposer);
+    env.events().publish(topics, swap_id);
+}
+
+pub(crate) fn metadata_revealed(env: &Env, metadata_uri: String) {
+    let topics = (symbol_short!("revealed"),);
+    env.events().publish(topics, metadata_uri);
+}
+
+pub(crate) fn refund(env: &Env, buyer: Address, item_id: u64, amount: u128) {
+    let topics = (symbol_short!("refund"), buyer);
+    env.events().publish(topics, (item_id, amount));
+}
+
+pub(crate) fn royalties_proposed(env: &Env, royalties: Vec<Royalty>, apply_ledger: u32) {
+    let topics = (symbol_short!("roy_prop"),);
+    env.events().publish(topics, (royalties, apply_ledger));
+}
+
+pub(crate) fn royalties_applied(env: &Env, royalties: Vec<Royalty>) {
+    let topics = (symbol_short!("roy_apply"),);
+    env.events().publish(topics, royalties);
+}
+
+pub(crate) fn item_royalties_proposed(
+    env: &Env,
+    item_id: u64,
+    royalties: Option<Vec<Royalty>>,
+    apply_ledger: u32,
+) {
+    let topics = (symbol_short!("item_prop"), item_id);
+    env.events().publish(topics, (royalties, apply_ledger));
+}
+
+pub(crate) fn item_royalties(env: &Env, item_id: u64, royalties: Option<Vec<Royalty>>) {
+    let topics = (symbol_short!("item_roy"), item_id);
+    env.events().publish(topics, royalties);
+}
+
+pub(crate) fn royalties_frozen(env: &Env) {
+    let topics = (symbol_short!("roy_frz"),);
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn bundle_listed(env: &Env, seller: Address, bundle_id: u64, price: u128) {
+    let topics = (symbol_short!("bndl_list"), seller);
+    env.events().publish(topics, (bundle_id, price));
+}
+
+pub(crate) fn bundle_cancelled(env: &Env, seller: Address, bundle_id: u64) {
+    let topics = (symbol_short!("bndl_cncl"), seller);
+    env.events().publish(topics, bundle_id);
+}
+
+pub(crate) fn bundle_bought(
+    env: &Env,
+    seller: Address,
+    buyer: Address,
+    bundle_id: u64,
+    price: u128,
+) {
+    let topics = (symbol_short!("bndl_buy"), seller, buyer);
+    env.events().publish(topics, (bundle_id, price));
+}
@@ -0,0 +1,32 @@
+use soroban_sdk::{contracttype, Address};
+
+/// An Auction is started by the owner of an Item, while it's active the Item can't be sold or transferred.
+/// The highest bid is kept by the contract and once someone outbids it, the amount can be withdrawn by the bidder.
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct Auction {
+    pub item_number: u64,
+    pub seller: Address,
+
+    /// The minimum amount the first bid needs to have
+    pub reserve: u128,
+
+    /// Each new bid needs to be at least this percentage higher than the current one, it uses basis points (100 = 1%)
+    pub min_increment_bps: u32,
+
+    /// Bids placed close to the end push this value so the auction can't be sniped at the last ledger
+    pub end_ledger: u32,
+
+    /// While nobody has placed a bid the highest bidder is the seller and the highest bid is "0"
+    pub highest_bidder: Address,
+    pub highest_bid: u128,
+}
+
+#[contracttype]
+pub enum AuctionsDataKeys {
+    Auction(u64),
+
+    /// The outbid amounts an Address can withdraw, across all the auctions
+    BidRefund(Address),
+}
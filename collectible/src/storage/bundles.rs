@@ -0,0 +1,24 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// A Bundle is a group of Items sold together for a single price, while the Bundle is active its Items can't be sold,
+/// transferred or used in any other operation.
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    pub id: u64,
+    pub seller: Address,
+    pub items: Vec<u64>,
+    pub price: u128,
+}
+
+#[contracttype]
+pub enum BundlesDataKeys {
+    Bundle(u64),
+
+    /// The id of the active Bundle an Item belongs to, it only exists while the Bundle is active
+    ItemBundle(u64),
+
+    /// The id of the last Bundle created, this value is kept in the instance storage
+    LastBundleId,
+}
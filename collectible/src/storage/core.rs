@@ -23,10 +23,31 @@ pub struct CoreData {
     pub initial_seller: Address,
 }
 
+/// The primary sale is only open between these ledgers (both included), by default it's open from the initialization
+/// of the contract and it never ends
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct PrimarySale {
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+
+    /// While paused, Items that haven't been minted can't be bought. Secondary sales are not affected
+    pub paused: bool,
+}
+
 #[contracttype]
 pub enum CoreDataKeys {
     CoreData,
 
     /// The Token Metadata is compatible with the metadata defined from the soroban-token-sdk
     TokenMetadata,
+
+    /// Kept apart from the CoreData so contracts initialized by previous versions can still read it, if it doesn't
+    /// exist the primary sale is open and not paused
+    PrimarySale,
+
+    /// For blind drops, the sha256 hash of the final metadata URI followed by a salt. While the metadata hasn't been
+    /// revealed the metadata URI is a placeholder
+    MetadataCommitment,
+    MetadataRevealed,
 }
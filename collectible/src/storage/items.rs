@@ -11,7 +11,39 @@ pub struct Item {
     pub price: u128,
 }
 
+/// The extra conditions of a sale offer, they are stored apart from the Item so Items saved by previous versions of
+/// the contract can still be read. An Item without a Listing record has a sale offer without conditions.
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct Listing {
+    /// If the sale offer has an expiration, once the ledger sequence is higher than this value the Item is no longer for sale
+    pub expiration_ledger: Option<u32>,
+
+    /// The sale offer can be reserved so only a specific Address can buy the Item
+    pub reserved_for: Reservation,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reservation {
+    None,
+    Buyer(Address),
+}
+
 #[contracttype]
 pub enum ItemsDataKeys {
     Item(u64),
+    Listing(u64),
+
+    /// The Items that haven't been minted are tracked as a list where each minted Item is replaced by the last one
+    /// Slots and positions that were never moved are not stored, the slot "i" holds the Item "i" by default
+    /// The size of the list is kept in the instance storage, by default it's "supply + 1"
+    UnmintedCount,
+    UnmintedSlot(u64),
+    UnmintedPosition(u64),
+
+    /// The number of Items minted since the contract was initialized, contracts initialized by previous versions don't
+    /// have it until the first mint or until the admin seeds it
+    /// This value is kept in the instance storage
+    MintedCount,
 }
@@ -1,4 +1,10 @@
+pub mod auctions;
 pub mod balances;
+pub mod bundles;
 pub mod core;
 pub mod items;
+pub mod offers;
 pub mod royalties;
+pub mod sales;
+pub mod swaps;
+pub mod vouchers;
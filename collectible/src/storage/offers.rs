@@ -0,0 +1,40 @@
+use soroban_sdk::{contracttype, Address};
+
+/// An Offer is a proposal to buy an Item that has already been minted, the offered amount is kept by the contract until
+/// the offer is accepted by the owner of the Item or cancelled by the bidder.
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct Offer {
+    pub bidder: Address,
+    pub item_number: u64,
+    pub amount: u128,
+
+    /// Once the ledger sequence is higher than this value the offer can't be accepted anymore, only cancelled
+    pub expiration_ledger: u32,
+}
+
+/// A CollectionBid is an offer for any Item of the collection, the owner of any minted Item can fill it.
+/// The contract keeps "amount * quantity" until the bid is filled or cancelled.
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct CollectionBid {
+    pub bidder: Address,
+
+    /// The amount paid for each Item
+    pub amount: u128,
+
+    /// How many Items the bidder still wants to buy, each fill reduces it by one
+    pub quantity: u32,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+pub enum OffersDataKeys {
+    /// An Address can only have one active Offer per Item, a new Offer replaces the previous one
+    Offer(u64, Address),
+
+    /// An Address can only have one active CollectionBid, a new CollectionBid replaces the previous one
+    CollectionBid(Address),
+}
@@ -1,17 +1,73 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, String, Vec};
 
+/// Each recipient has a percentage for the first sale of an Item and another one for every sale after that, a
+/// percentage of "0" means the recipient doesn't get paid on those sales
 #[contracttype]
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Royalty {
+    pub address: Address,
+    pub name: String,
+    pub primary_percentage: u128,
+    pub secondary_percentage: u128,
+}
+
+/// The format of the royalties before they had separate percentages, a "first_sale" royalty was only paid on the first
+/// sale and the rest were paid on every sale. This is only used to migrate the royalties of existing collectibles
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyRoyalty {
     pub address: Address,
     pub first_sale: bool,
     pub name: String,
     pub percentage: u128,
 }
 
+/// A new list of royalties proposed by the admin, it can be applied once the ledger sequence reaches "apply_ledger"
+#[contracttype]
+#[derive(Clone)]
+pub struct PendingRoyalties {
+    pub royalties: Vec<Royalty>,
+    pub apply_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum ItemRoyaltiesUpdate {
+    /// The Item gets its own royalties
+    Override(Vec<Royalty>),
+
+    /// The Item goes back to the royalties of the collection
+    RemoveOverride,
+}
+
+/// New royalties for a specific Item proposed by the admin, they follow the same delay as the collection royalties
+#[contracttype]
+#[derive(Clone)]
+pub struct PendingItemRoyalties {
+    pub update: ItemRoyaltiesUpdate,
+    pub apply_ledger: u32,
+}
+
 #[contracttype]
 pub enum RoyaltiesDataKeys {
     /// The Royalties defined for the collectible, this returns a Map<Royalty>
     /// This value isn't kept as part of the instance storage and instead is defined as permanent
     Royalties,
+
+    PendingRoyalties,
+
+    /// The number of ledgers between proposing new royalties and applying them, this value is kept in the instance
+    /// storage
+    RoyaltiesDelay,
+
+    /// Once frozen the royalties can't be changed anymore, this value is kept in the instance storage
+    RoyaltiesFrozen,
+
+    /// The royalties of a specific Item, they replace the royalties of the collection when the Item is sold
+    ItemRoyalties(u64),
+    PendingItemRoyalties(u64),
+
+    /// The version of the royalties format, collectibles created before this value existed use the legacy format
+    /// This value is kept in the instance storage
+    RoyaltiesVersion,
 }
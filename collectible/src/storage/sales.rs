@@ -0,0 +1,128 @@
+use crate::storage::royalties::Royalty;
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
+
+/// A declining price schedule for the primary sale, before the start ledger the price is the start price and after it
+/// the price goes down on each ledger until it reaches the floor price.
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct DutchAuction {
+    pub start_price: u128,
+    pub floor_price: u128,
+    pub start_ledger: u32,
+    pub decay_per_ledger: u128,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, PartialEq)]
+pub enum BondingCurveKind {
+    /// The price goes up by "increment" with each Item minted
+    Linear,
+
+    /// The price goes up by a percentage with each Item minted, "increment" uses the same format as the royalties
+    /// percentage IE 0_0100000 is 1%
+    Exponential,
+}
+
+/// A price schedule for the primary sale that depends on the number of Items that have already been minted
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct BondingCurve {
+    pub kind: BondingCurveKind,
+    pub base_price: u128,
+    pub increment: u128,
+}
+
+/// Defines who can buy during a sale phase
+#[contracttype]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Allowlist {
+    Public,
+
+    /// Only the addresses included in the Merkle tree can buy, each leaf is the sha256 hash of the address XDR and each
+    /// node is the sha256 hash of its two children sorted
+    MerkleRoot(BytesN<32>),
+}
+
+/// A phase of the primary sale, it starts at "start_ledger" and lasts until the next phase starts
+/// If "price" is not defined the phase uses the price tiers, the bonding curve, the Dutch auction or the initial price
+/// If "max_per_wallet" is defined it replaces the collection limit while the phase is active
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct SalePhase {
+    pub start_ledger: u32,
+    pub price: Option<u128>,
+    pub allowlist: Allowlist,
+    pub max_per_wallet: Option<u32>,
+}
+
+/// The primary price of the Items from "from" to "to" (both included)
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct PriceTier {
+    pub from: u64,
+    pub to: u64,
+    pub price: u128,
+}
+
+/// The Items from "from" to "to" (both included) are withheld from the primary sale
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct ReservedItems {
+    pub from: u64,
+    pub to: u64,
+}
+
+/// The payment of a primary purchase kept by the contract while the buyer can still ask for a refund
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct EscrowedSale {
+    pub item_number: u64,
+    pub buyer: Address,
+    pub price: u128,
+
+    /// The last ledger where the buyer can ask for a refund
+    pub refund_deadline: u32,
+
+    /// The royalties of the Item when it was bought, these are the ones paid when the proceeds are released
+    pub royalties: Vec<Royalty>,
+}
+
+#[contracttype]
+pub enum SalesDataKeys {
+    /// If defined, the primary sale uses this schedule instead of the initial price from the CoreData
+    /// This value is kept in the instance storage
+    DutchAuction,
+
+    /// If defined, the primary sale uses this curve instead of the initial price from the CoreData
+    /// This value is kept in the instance storage
+    BondingCurve,
+
+    /// The phases of the primary sale sorted by their start ledger, if there are phases the primary sale doesn't start
+    /// until the first one starts. This value is kept in the instance storage
+    SalePhases,
+
+    /// The maximum number of Items an Address can buy in the primary sale, if it's not defined there is no limit
+    /// This value is kept in the instance storage
+    MaxPerWallet,
+
+    /// The number of Items an Address has bought in the primary sale, it's not affected by transfers
+    PrimaryPurchases(Address),
+
+    /// The price tiers sorted by their item numbers, they can't overlap
+    /// This value is kept in the instance storage
+    PriceTiers,
+
+    /// The primary price of a specific Item, it takes precedence over the price tiers
+    PriceOverride(u64),
+
+    /// The ranges of Items that can only be distributed with the "mint" function
+    /// This value is kept in the instance storage
+    ReservedItems,
+
+    /// The number of ledgers the payment of a primary purchase is kept in escrow, if it's not defined the payment is
+    /// sent right away. This value is kept in the instance storage
+    RefundWindow,
+
+    EscrowedSale(u64),
+}
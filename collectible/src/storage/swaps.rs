@@ -0,0 +1,40 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// A Swap is a proposal to exchange Items between two accounts, the ownership of all the Items changes at the same time
+/// once the counterparty accepts it.
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct Swap {
+    pub id: u64,
+    pub proposer: Address,
+    pub counterparty: Address,
+    pub proposer_items: Vec<u64>,
+    pub counterparty_items: Vec<u64>,
+
+    /// An amount the proposer adds on top of its Items, it's kept by the contract until the Swap is accepted or cancelled
+    /// "0" means the Swap doesn't include a payment
+    pub payment: u128,
+}
+
+/// Defines if the payment added to a Swap pays royalties
+#[contracttype]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwapRoyaltyPolicy {
+    /// Swaps never pay royalties, the payment goes entirely to the counterparty
+    NoRoyalties,
+
+    /// The payment pays the royalties of a second sale and the rest goes to the counterparty
+    RoyaltiesOnPayment,
+}
+
+#[contracttype]
+pub enum SwapsDataKeys {
+    Swap(u64),
+
+    /// The id of the last Swap created, this value is kept in the instance storage
+    LastSwapId,
+
+    /// This value is kept in the instance storage, if it's not defined the policy is "NoRoyalties"
+    RoyaltyPolicy,
+}
@@ -0,0 +1,27 @@
+use soroban_sdk::{contracttype, Address};
+
+/// A Voucher lets the recipient mint an Item without the initial_seller having to sign the transaction, instead the
+/// initial_seller signs the Voucher off-chain with the key registered in the contract.
+/// The signed message is the XDR of the tuple (contract address, Voucher)
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct Voucher {
+    pub item_number: u64,
+    pub recipient: Address,
+
+    /// The amount the recipient pays to the initial_seller, it can be "0" for giveaways
+    pub price: u128,
+    pub expiration_ledger: u32,
+
+    /// Each nonce can only be used once
+    pub nonce: u64,
+}
+
+#[contracttype]
+pub enum VouchersDataKeys {
+    /// The ed25519 public key used to verify the Vouchers, this value is kept in the instance storage
+    Signer,
+
+    UsedNonce(u64),
+}
@@ -1,8 +1,14 @@
+pub mod test_auctions;
 pub mod test_balances;
+pub mod test_bundles;
 pub mod test_buy;
 pub mod test_core;
 pub mod test_items;
+pub mod test_offers;
 pub mod test_royalties;
+pub mod test_sales;
 pub mod test_sell;
+pub mod test_swaps;
 pub mod test_transfers;
 pub mod test_utils;
+pub mod test_vouchers;
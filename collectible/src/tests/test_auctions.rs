@@ -0,0 +1,180 @@
+#![cfg(test)]
+
+use crate::errors::SCErrors;
+use crate::storage::auctions::Auction;
+use crate::storage::items::Item;
+use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, Env};
+
+#[test]
+pub fn test_auction_bids_and_settlement() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let owner: Address = Address::generate(&env);
+    test_data.contract_client.mint(&0, &owner);
+
+    // 10% minimum increment and the auction ends at ledger 1000
+    test_data
+        .contract_client
+        .start_auction(&0, &10_0000000, &1000, &1000);
+
+    let locked_error = test_data
+        .contract_client
+        .try_sell(&0, &50_0000000, &None, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(locked_error, SCErrors::ItemIsInAuction.into());
+
+    let first_bidder: Address = Address::generate(&env);
+    let second_bidder: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&first_bidder, &100_0000000);
+    test_data
+        .usd_token_admin_client
+        .mint(&second_bidder, &100_0000000);
+
+    let seller_bid_error = test_data
+        .contract_client
+        .try_bid(&owner, &0, &10_0000000)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(seller_bid_error, SCErrors::BidderIsTheSeller.into());
+
+    let under_reserve_error = test_data
+        .contract_client
+        .try_bid(&first_bidder, &0, &9_0000000)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(under_reserve_error, SCErrors::BidIsTooLow.into());
+
+    test_data
+        .contract_client
+        .bid(&first_bidder, &0, &10_0000000);
+
+    let low_increment_error = test_data
+        .contract_client
+        .try_bid(&second_bidder, &0, &10_5000000)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(low_increment_error, SCErrors::BidIsTooLow.into());
+
+    test_data
+        .contract_client
+        .bid(&second_bidder, &0, &50_0000000);
+
+    // The first bid stays in the contract until the first bidder withdraws it
+    assert_eq!(
+        test_data.usd_token_client.balance(&first_bidder),
+        90_0000000
+    );
+    assert_eq!(
+        test_data.contract_client.bid_refund(&first_bidder),
+        10_0000000
+    );
+    assert_eq!(
+        test_data.contract_client.withdraw_bid_refund(&first_bidder),
+        10_0000000
+    );
+    assert_eq!(test_data.contract_client.bid_refund(&first_bidder), 0);
+    assert_eq!(
+        test_data.contract_client.withdraw_bid_refund(&first_bidder),
+        0
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&first_bidder),
+        100_0000000
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.contract_client.address),
+        50_0000000
+    );
+
+    let not_ended_error = test_data
+        .contract_client
+        .try_settle_auction(&0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_ended_error, SCErrors::AuctionHasNotEnded.into());
+
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = 1001);
+    test_data.contract_client.settle_auction(&0);
+
+    let item: Item = test_data.contract_client.item(&0);
+    assert_eq!(&item.owner, &second_bidder);
+    assert_eq!(test_data.contract_client.balance(&owner), 0);
+    assert_eq!(test_data.contract_client.balance(&second_bidder), 1);
+
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        1_5000000
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.charity_royalty.address),
+        1_0000000
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&owner),
+        50_0000000 - 1_5000000 - 1_0000000
+    );
+
+    let removed_error = test_data
+        .contract_client
+        .try_auction(&0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(removed_error, SCErrors::AuctionDoesNotExist.into());
+}
+
+#[test]
+pub fn test_auction_anti_sniping_extension() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let owner: Address = Address::generate(&env);
+    test_data.contract_client.mint(&0, &owner);
+    test_data
+        .contract_client
+        .start_auction(&0, &1_0000000, &0, &1000);
+
+    let bidder: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&bidder, &1_0000000);
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 990);
+    test_data.contract_client.bid(&bidder, &0, &1_0000000);
+
+    let auction: Auction = test_data.contract_client.auction(&0);
+    assert_eq!(auction.end_ledger, 990 + 120);
+
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = 1001);
+
+    let not_ended_error = test_data
+        .contract_client
+        .try_settle_auction(&0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_ended_error, SCErrors::AuctionHasNotEnded.into());
+}
@@ -0,0 +1,154 @@
+#![cfg(test)]
+
+use crate::errors::SCErrors;
+use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, Env};
+
+#[test]
+pub fn test_list_and_buy_bundle() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let seller: Address = Address::generate(&env);
+    test_data.contract_client.mint(&1, &seller);
+    test_data.contract_client.mint(&2, &seller);
+    test_data
+        .contract_client
+        .sell(&1, &30_0000000, &None, &None);
+
+    let bundle_id: u64 =
+        test_data
+            .contract_client
+            .list_bundle(&seller, &vec![&env, 1, 2], &100_0000000);
+
+    // The individual sale offer was removed and the Items are locked
+    assert_eq!(test_data.contract_client.item(&1).for_sale, false);
+
+    let locked_error = test_data
+        .contract_client
+        .try_sell(&2, &10_0000000, &None, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(locked_error, SCErrors::ItemIsInBundle.into());
+
+    let transfer_error = test_data
+        .contract_client
+        .try_transfer(&2, &Address::generate(&env))
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(transfer_error, SCErrors::ItemIsInBundle.into());
+
+    let buyer: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&buyer, &100_0000000);
+
+    let max_price_error = test_data
+        .contract_client
+        .try_buy_bundle(&buyer, &bundle_id, &99_0000000)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(max_price_error, SCErrors::PriceIsHigherThanMaxPrice.into());
+
+    test_data
+        .contract_client
+        .buy_bundle(&buyer, &bundle_id, &100_0000000);
+
+    assert_eq!(test_data.contract_client.item(&1).owner, buyer);
+    assert_eq!(test_data.contract_client.item(&2).owner, buyer);
+    assert_eq!(test_data.contract_client.balance(&buyer), 2);
+    assert_eq!(test_data.contract_client.balance(&seller), 0);
+
+    // None of the Items has its own royalties so they are paid once over the total price (3% + 2%)
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        3_0000000
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&seller),
+        100_0000000 - 3_0000000 - 2_0000000
+    );
+
+    // Once sold, the Items are no longer locked
+    test_data
+        .contract_client
+        .sell(&1, &30_0000000, &None, &None);
+}
+
+#[test]
+pub fn test_bundle_royalties_with_uneven_shares() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let seller: Address = Address::generate(&env);
+    test_data.contract_client.mint(&1, &seller);
+    test_data.contract_client.mint(&2, &seller);
+    test_data.contract_client.mint(&3, &seller);
+
+    // The price can't be split evenly and each share is too small to pay royalties on its own
+    let bundle_id: u64 = test_data
+        .contract_client
+        .list_bundle(&seller, &vec![&env, 1, 2, 3], &100);
+
+    let buyer: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&buyer, &100);
+    test_data
+        .contract_client
+        .buy_bundle(&buyer, &bundle_id, &100);
+
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        3
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.charity_royalty.address),
+        2
+    );
+    assert_eq!(test_data.usd_token_client.balance(&seller), 95);
+}
+
+#[test]
+pub fn test_cancel_bundle() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let seller: Address = Address::generate(&env);
+    test_data.contract_client.mint(&1, &seller);
+
+    let bundle_id: u64 =
+        test_data
+            .contract_client
+            .list_bundle(&seller, &vec![&env, 1], &100_0000000);
+
+    test_data.contract_client.cancel_bundle(&bundle_id);
+
+    let does_not_exist_error = test_data
+        .contract_client
+        .try_bundle(&bundle_id)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(does_not_exist_error, SCErrors::BundleDoesNotExist.into());
+
+    test_data
+        .contract_client
+        .sell(&1, &30_0000000, &None, &None);
+    assert_eq!(test_data.contract_client.item(&1).for_sale, true);
+}
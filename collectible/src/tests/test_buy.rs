@@ -1,10 +1,10 @@
 #![cfg(test)]
 
 use crate::errors::SCErrors;
-use crate::storage::items::Item;
+use crate::storage::items::{Item, Reservation};
 use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{vec, Address, Env, Vec};
 
 #[test]
 pub fn test_initial_sale_and_invalid_number() {
@@ -19,7 +19,9 @@ pub fn test_initial_sale_and_invalid_number() {
         .usd_token_admin_client
         .mint(&buyer, &(test_data.initial_price as i128));
 
-    test_data.contract_client.buy(&buyer, &0);
+    test_data
+        .contract_client
+        .buy(&buyer, &0, &test_data.initial_price, &None);
 
     let item: Item = test_data.contract_client.item(&0);
     let buyer_balance: u128 = test_data.contract_client.balance(&&buyer);
@@ -32,7 +34,12 @@ pub fn test_initial_sale_and_invalid_number() {
     let new_buyer: Address = Address::generate(&env);
     let not_for_sale_error = test_data
         .contract_client
-        .try_buy(&new_buyer, &(test_data.supply + 100))
+        .try_buy(
+            &new_buyer,
+            &(test_data.supply + 100),
+            &test_data.initial_price,
+            &None,
+        )
         .unwrap_err()
         .unwrap();
 
@@ -52,7 +59,9 @@ pub fn test_second_sales_and_not_sale() {
         .usd_token_admin_client
         .mint(&buyer, &(test_data.initial_price as i128));
 
-    test_data.contract_client.buy(&buyer, &0);
+    test_data
+        .contract_client
+        .buy(&buyer, &0, &test_data.initial_price, &None);
     let mut item: Item = test_data.contract_client.item(&0);
     assert_eq!(&item.owner, &buyer);
 
@@ -61,19 +70,23 @@ pub fn test_second_sales_and_not_sale() {
     let new_buyer: Address = Address::generate(&env);
     let not_for_sale_error = test_data
         .contract_client
-        .try_buy(&new_buyer, &0)
+        .try_buy(&new_buyer, &0, &50_0000000, &None)
         .unwrap_err()
         .unwrap();
 
     assert_eq!(not_for_sale_error, SCErrors::ItemIsNotForSale.into());
 
-    test_data.contract_client.sell(&0, &50_0000000);
+    test_data
+        .contract_client
+        .sell(&0, &50_0000000, &None, &None);
 
     test_data
         .usd_token_admin_client
         .mint(&new_buyer, &50_0000000);
 
-    test_data.contract_client.buy(&new_buyer, &0);
+    test_data
+        .contract_client
+        .buy(&new_buyer, &0, &50_0000000, &None);
     item = test_data.contract_client.item(&0);
     assert_eq!(&item.owner, &new_buyer);
     assert_eq!(&item.for_sale, &false);
@@ -82,3 +95,224 @@ pub fn test_second_sales_and_not_sale() {
     assert_eq!(test_data.contract_client.balance(&buyer), 0u128);
     assert_eq!(test_data.contract_client.balance(&new_buyer), 1u128);
 }
+
+#[test]
+pub fn test_buying_reserved_listing() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let owner: Address = Address::generate(&env);
+    test_data.contract_client.mint(&0, &owner);
+
+    let reserved_buyer: Address = Address::generate(&env);
+    test_data
+        .contract_client
+        .sell(&0, &50_0000000, &None, &Some(reserved_buyer.clone()));
+
+    let other_buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&other_buyer, &50_0000000);
+
+    let reserved_error = test_data
+        .contract_client
+        .try_buy(&other_buyer, &0, &50_0000000, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        reserved_error,
+        SCErrors::ItemIsReservedForAnotherBuyer.into()
+    );
+
+    test_data
+        .usd_token_admin_client
+        .mint(&reserved_buyer, &50_0000000);
+    test_data
+        .contract_client
+        .buy(&reserved_buyer, &0, &50_0000000, &None);
+
+    let item: Item = test_data.contract_client.item(&0);
+    assert_eq!(&item.owner, &reserved_buyer);
+    assert_eq!(
+        &test_data.contract_client.listing(&0).reserved_for,
+        &Reservation::None
+    );
+
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        1_5000000
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&owner),
+        50_0000000 - 1_5000000 - 1_0000000
+    );
+}
+
+#[test]
+pub fn test_buying_above_max_price() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128));
+
+    let primary_price_error = test_data
+        .contract_client
+        .try_buy(&buyer, &0, &(test_data.initial_price - 1), &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        primary_price_error,
+        SCErrors::PriceIsHigherThanMaxPrice.into()
+    );
+
+    test_data
+        .contract_client
+        .buy(&buyer, &0, &test_data.initial_price, &None);
+
+    // The seller raises the price before the buyer transaction is executed
+    test_data
+        .contract_client
+        .sell(&0, &80_0000000, &None, &None);
+
+    let new_buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&new_buyer, &80_0000000);
+
+    let secondary_price_error = test_data
+        .contract_client
+        .try_buy(&new_buyer, &0, &50_0000000, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        secondary_price_error,
+        SCErrors::PriceIsHigherThanMaxPrice.into()
+    );
+    assert_eq!(test_data.contract_client.item(&0).owner, buyer);
+}
+
+#[test]
+pub fn test_buy_many() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let owner: Address = Address::generate(&env);
+    test_data.contract_client.mint(&10, &owner);
+    test_data.contract_client.mint(&11, &owner);
+    test_data
+        .contract_client
+        .sell(&10, &50_0000000, &None, &None);
+
+    let buyer: Address = Address::generate(&env);
+    let total: u128 = (test_data.initial_price * 2) + 50_0000000;
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(total as i128));
+
+    // Item "11" is not for sale so nothing should be bought
+    let not_for_sale_error = test_data
+        .contract_client
+        .try_buy_many(&buyer, &vec![&env, 0, 10, 20, 11], &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_for_sale_error, SCErrors::ItemIsNotForSale.into());
+    assert_eq!(test_data.contract_client.balance(&buyer), 0);
+
+    let max_total_error = test_data
+        .contract_client
+        .try_buy_many(&buyer, &vec![&env, 0, 10, 20], &(total - 1), &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(max_total_error, SCErrors::PriceIsHigherThanMaxPrice.into());
+
+    test_data
+        .contract_client
+        .buy_many(&buyer, &vec![&env, 0, 10, 20], &total, &None);
+
+    assert_eq!(test_data.contract_client.balance(&buyer), 3);
+    assert_eq!(test_data.contract_client.balance(&owner), 1);
+    assert_eq!(test_data.usd_token_client.balance(&buyer), 0);
+
+    // Two first sales (1% + 3% + 2%) and one second sale (3% + 2%)
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.platform_royalty.address),
+        0_1999000 * 2
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        (0_5997000 * 2) + 1_5000000
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&owner),
+        50_0000000 - 1_5000000 - 1_0000000
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.initial_seller) as u128,
+        (test_data.initial_price - 0_1999000 - 0_5997000 - 0_3998000) * 2
+    );
+}
+
+#[test]
+pub fn test_buy_random() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let mut test_data: TestData = create_test_data(&env);
+    test_data.supply = 3;
+    init_with_test_data(&test_data);
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128 * 3));
+
+    // Items minted directly are never picked
+    test_data.contract_client.mint(&1, &test_data.admin);
+
+    let mut bought: Vec<u64> = vec![&env];
+    for _ in 0..3 {
+        let item_number: u64 =
+            test_data
+                .contract_client
+                .buy_random(&buyer, &test_data.initial_price, &None);
+
+        assert_ne!(item_number, 1);
+        assert!(!bought.contains(item_number));
+        assert_eq!(test_data.contract_client.item(&item_number).owner, buyer);
+        bought.push_back(item_number);
+    }
+
+    let sold_out_error = test_data
+        .contract_client
+        .try_buy_random(&buyer, &test_data.initial_price, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(sold_out_error, SCErrors::AllItemsAreMinted.into());
+    assert_eq!(test_data.contract_client.balance(&buyer), 3);
+}
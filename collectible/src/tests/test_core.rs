@@ -1,8 +1,8 @@
 #![cfg(test)]
 
 use crate::errors::SCErrors;
-use crate::tests::test_utils::{create_test_data, TestData};
-use soroban_sdk::Env;
+use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
+use soroban_sdk::{Bytes, BytesN, Env, String};
 
 #[test]
 pub fn test_init() {
@@ -17,10 +17,9 @@ pub fn test_init() {
         &test_data.initial_price,
         &test_data.initial_seller,
         &test_data.usd_token_client.address,
-        &test_data.token_metadata.name,
-        &test_data.token_metadata.symbol,
-        &test_data.token_metadata.metadata_uri,
+        &test_data.token_metadata,
         &test_data.default_royalties,
+        &None,
     );
 
     assert_eq!(test_data.contract_client.supply(), test_data.supply);
@@ -33,13 +32,86 @@ pub fn test_init() {
             &test_data.initial_price,
             &test_data.initial_seller,
             &test_data.usd_token_client.address,
-            &test_data.token_metadata.name,
-            &test_data.token_metadata.symbol,
-            &test_data.token_metadata.metadata_uri,
+            &test_data.token_metadata,
             &test_data.default_royalties,
+            &None,
         )
         .unwrap_err()
         .unwrap();
 
     assert_eq!(already_initiated_error, SCErrors::AlreadyInitialized.into());
 }
+
+#[test]
+pub fn test_commit_and_reveal_metadata() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+
+    let final_uri: String = String::from_str(&env, "ipfs://final-metadata");
+    let salt: Bytes = Bytes::from_slice(&env, b"secret-salt");
+    let mut preimage: Bytes = Bytes::from_slice(&env, b"ipfs://final-metadata");
+    preimage.append(&salt);
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage);
+
+    test_data.contract_client.init(
+        &test_data.admin,
+        &test_data.supply,
+        &test_data.initial_price,
+        &test_data.initial_seller,
+        &test_data.usd_token_client.address,
+        &test_data.token_metadata,
+        &test_data.default_royalties,
+        &Some(commitment),
+    );
+
+    let wrong_salt_error = test_data
+        .contract_client
+        .try_reveal(&final_uri, &Bytes::from_slice(&env, b"wrong-salt"))
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(wrong_salt_error, SCErrors::InvalidMetadataReveal.into());
+    assert!(!test_data.contract_client.revealed());
+    assert_eq!(
+        test_data.contract_client.metadata_uri(),
+        test_data.token_metadata.metadata_uri
+    );
+
+    test_data.contract_client.reveal(&final_uri, &salt);
+
+    assert!(test_data.contract_client.revealed());
+    assert_eq!(test_data.contract_client.metadata_uri(), final_uri);
+
+    let already_revealed_error = test_data
+        .contract_client
+        .try_reveal(&final_uri, &salt)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        already_revealed_error,
+        SCErrors::MetadataIsAlreadyRevealed.into()
+    );
+}
+
+#[test]
+pub fn test_reveal_without_commitment() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let not_committed_error = test_data
+        .contract_client
+        .try_reveal(
+            &String::from_str(&env, "ipfs://final-metadata"),
+            &Bytes::from_slice(&env, b"secret-salt"),
+        )
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_committed_error, SCErrors::MetadataIsNotCommitted.into());
+}
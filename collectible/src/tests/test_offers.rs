@@ -0,0 +1,219 @@
+#![cfg(test)]
+
+use crate::errors::SCErrors;
+use crate::storage::items::Item;
+use crate::storage::offers::Offer;
+use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, Env};
+
+#[test]
+pub fn test_make_and_cancel_offer() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let bidder: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&bidder, &50_0000000);
+
+    let not_minted_error = test_data
+        .contract_client
+        .try_make_offer(&bidder, &0, &10_0000000, &100)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_minted_error, SCErrors::ItemHasNotBeenMinted.into());
+
+    test_data.contract_client.mint(&0, &Address::generate(&env));
+
+    let invalid_expiration_error = test_data
+        .contract_client
+        .try_make_offer(&bidder, &0, &10_0000000, &0)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        invalid_expiration_error,
+        SCErrors::InvalidExpirationLedger.into()
+    );
+
+    test_data
+        .contract_client
+        .make_offer(&bidder, &0, &10_0000000, &100);
+
+    assert_eq!(test_data.usd_token_client.balance(&bidder), 40_0000000);
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.contract_client.address),
+        10_0000000
+    );
+
+    // A new offer replaces the previous one and only the new amount stays in escrow
+    test_data
+        .contract_client
+        .make_offer(&bidder, &0, &20_0000000, &100);
+
+    let offer: Offer = test_data.contract_client.offer(&0, &bidder);
+    assert_eq!(offer.amount, 20_0000000);
+    assert_eq!(test_data.usd_token_client.balance(&bidder), 30_0000000);
+
+    test_data.contract_client.cancel_offer(&bidder, &0);
+
+    assert_eq!(test_data.usd_token_client.balance(&bidder), 50_0000000);
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.contract_client.address),
+        0
+    );
+
+    let does_not_exist_error = test_data
+        .contract_client
+        .try_offer(&0, &bidder)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(does_not_exist_error, SCErrors::OfferDoesNotExist.into());
+}
+
+#[test]
+pub fn test_accept_offer() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let owner: Address = Address::generate(&env);
+    test_data.contract_client.mint(&0, &owner);
+
+    let bidder: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&bidder, &50_0000000);
+    test_data
+        .contract_client
+        .make_offer(&bidder, &0, &50_0000000, &100);
+
+    test_data.contract_client.accept_offer(&0, &bidder);
+
+    let item: Item = test_data.contract_client.item(&0);
+    assert_eq!(&item.owner, &bidder);
+    assert_eq!(test_data.contract_client.balance(&owner), 0);
+    assert_eq!(test_data.contract_client.balance(&bidder), 1);
+
+    // The platform royalty is only for first sales so it doesn't receive anything
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.platform_royalty.address),
+        0
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        1_5000000
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.charity_royalty.address),
+        1_0000000
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&owner),
+        50_0000000 - 1_5000000 - 1_0000000
+    );
+
+    test_data.usd_token_admin_client.mint(&owner, &10_0000000);
+    test_data
+        .contract_client
+        .make_offer(&owner, &0, &10_0000000, &100);
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 101);
+
+    let expired_error = test_data
+        .contract_client
+        .try_accept_offer(&0, &owner)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(expired_error, SCErrors::OfferHasExpired.into());
+}
+
+#[test]
+pub fn test_collection_bids() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let first_owner: Address = Address::generate(&env);
+    let second_owner: Address = Address::generate(&env);
+    test_data.contract_client.mint(&3, &first_owner);
+    test_data.contract_client.mint(&7, &second_owner);
+
+    let bidder: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&bidder, &100_0000000);
+
+    let invalid_quantity_error = test_data
+        .contract_client
+        .try_place_collection_bid(&bidder, &10_0000000, &0, &100)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(invalid_quantity_error, SCErrors::InvalidAmount.into());
+
+    test_data
+        .contract_client
+        .place_collection_bid(&bidder, &10_0000000, &3, &100);
+
+    assert_eq!(test_data.usd_token_client.balance(&bidder), 70_0000000);
+
+    test_data.contract_client.accept_collection_bid(&3, &bidder);
+    test_data.contract_client.accept_collection_bid(&7, &bidder);
+
+    assert_eq!(test_data.contract_client.item(&3).owner, bidder);
+    assert_eq!(test_data.contract_client.item(&7).owner, bidder);
+    assert_eq!(test_data.contract_client.balance(&bidder), 2);
+    assert_eq!(
+        test_data.contract_client.collection_bid(&bidder).quantity,
+        1
+    );
+
+    // Each fill pays the 3% and 2% second sale royalties
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        0_3000000 * 2
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&first_owner),
+        10_0000000 - 0_3000000 - 0_2000000
+    );
+
+    test_data.contract_client.cancel_collection_bid(&bidder);
+
+    assert_eq!(test_data.usd_token_client.balance(&bidder), 80_0000000);
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.contract_client.address),
+        0
+    );
+
+    let does_not_exist_error = test_data
+        .contract_client
+        .try_accept_collection_bid(&3, &bidder)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        does_not_exist_error,
+        SCErrors::CollectionBidDoesNotExist.into()
+    );
+}
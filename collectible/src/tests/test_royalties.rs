@@ -1,8 +1,13 @@
 #![cfg(test)]
 
+use crate::errors::SCRoyaltyErrors;
+use crate::storage::core::CoreDataKeys;
+use crate::storage::items::ItemsDataKeys;
+use crate::storage::royalties::{LegacyRoyalty, RoyaltiesDataKeys, Royalty};
 use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env};
+use crate::utils::royalties::DEFAULT_ROYALTIES_DELAY;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{contracttype, vec, Address, Env, String, Vec};
 
 #[test]
 pub fn test_royalties_and_payments() {
@@ -17,7 +22,9 @@ pub fn test_royalties_and_payments() {
         .usd_token_admin_client
         .mint(&buyer, &(test_data.initial_price as i128));
 
-    test_data.contract_client.buy(&buyer, &0);
+    test_data
+        .contract_client
+        .buy(&buyer, &0, &test_data.initial_price, &None);
 
     assert_eq!(
         test_data
@@ -44,7 +51,9 @@ pub fn test_royalties_and_payments() {
         test_data.initial_price - 0_1999000 - 0_5997000 - 0_3998000
     );
 
-    test_data.contract_client.sell(&0, &50_0000000);
+    test_data
+        .contract_client
+        .sell(&0, &50_0000000, &None, &None);
 
     let new_buyer: Address = Address::generate(&env);
 
@@ -52,7 +61,9 @@ pub fn test_royalties_and_payments() {
         .usd_token_admin_client
         .mint(&new_buyer, &50_0000000);
 
-    test_data.contract_client.buy(&new_buyer, &0);
+    test_data
+        .contract_client
+        .buy(&new_buyer, &0, &50_0000000, &None);
 
     // Platform doesn't receive more funds because we set it as a first sale royalty.
     // It doesn't receive royalties for second sales
@@ -81,3 +92,542 @@ pub fn test_royalties_and_payments() {
         test_data.initial_price - 0_1999000 - 0_5997000 - 0_3998000
     );
 }
+
+#[test]
+pub fn test_invalid_royalties_at_init() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+
+    let try_init_with = |royalties: &Vec<Royalty>| {
+        test_data
+            .contract_client
+            .try_init(
+                &test_data.admin,
+                &test_data.supply,
+                &test_data.initial_price,
+                &test_data.initial_seller,
+                &test_data.usd_token_client.address,
+                &test_data.token_metadata,
+                royalties,
+                &None,
+            )
+            .unwrap_err()
+            .unwrap()
+    };
+
+    let mut too_high: Royalty = test_data.creator_royalty.clone();
+    too_high.primary_percentage = 9900001;
+    assert_eq!(
+        try_init_with(&vec![&env, test_data.platform_royalty.clone(), too_high]),
+        SCRoyaltyErrors::RoyaltiesAreHigherThanPrice.into()
+    );
+
+    let mut duplicated: Royalty = test_data.charity_royalty.clone();
+    duplicated.address = test_data.creator_royalty.address.clone();
+    assert_eq!(
+        try_init_with(&vec![&env, test_data.creator_royalty.clone(), duplicated]),
+        SCRoyaltyErrors::RoyaltyAddressIsDuplicated.into()
+    );
+
+    let mut zero: Royalty = test_data.creator_royalty.clone();
+    zero.primary_percentage = 0;
+    zero.secondary_percentage = 0;
+    assert_eq!(
+        try_init_with(&vec![&env, zero]),
+        SCRoyaltyErrors::RoyaltyPercentageIsZero.into()
+    );
+
+    let mut unnamed: Royalty = test_data.creator_royalty.clone();
+    unnamed.name = String::from_str(&env, "");
+    assert_eq!(
+        try_init_with(&vec![&env, unnamed]),
+        SCRoyaltyErrors::RoyaltyNameIsEmpty.into()
+    );
+
+    let mut too_many: Vec<Royalty> = Vec::new(&env);
+    for _ in 0..11 {
+        let mut royalty: Royalty = test_data.creator_royalty.clone();
+        royalty.address = Address::generate(&env);
+        royalty.primary_percentage = 1000;
+        too_many.push_back(royalty);
+    }
+    assert_eq!(
+        try_init_with(&too_many),
+        SCRoyaltyErrors::TooManyRoyalties.into()
+    );
+
+    // A valid configuration can still be used after the failed attempts
+    init_with_test_data(&test_data);
+    assert_eq!(test_data.contract_client.royalties().len(), 3);
+}
+
+#[test]
+pub fn test_royalties_update_with_timelock_and_freeze() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let mut new_charity: Royalty = test_data.charity_royalty.clone();
+    new_charity.address = Address::generate(&env);
+    let new_royalties: Vec<Royalty> = vec![
+        &env,
+        test_data.platform_royalty.clone(),
+        test_data.creator_royalty.clone(),
+        new_charity.clone(),
+    ];
+
+    test_data.contract_client.propose_royalties(&new_royalties);
+    assert_eq!(
+        test_data.contract_client.pending_royalties().apply_ledger,
+        DEFAULT_ROYALTIES_DELAY
+    );
+
+    let timelock_error = test_data
+        .contract_client
+        .try_apply_royalties()
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        timelock_error,
+        SCRoyaltyErrors::RoyaltiesTimelockIsActive.into()
+    );
+
+    let decrease_error = test_data
+        .contract_client
+        .try_set_royalties_delay(&(DEFAULT_ROYALTIES_DELAY - 1))
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        decrease_error,
+        SCRoyaltyErrors::RoyaltiesDelayCantDecrease.into()
+    );
+
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = DEFAULT_ROYALTIES_DELAY);
+    test_data.contract_client.apply_royalties();
+
+    assert_eq!(
+        test_data
+            .contract_client
+            .royalties()
+            .get(2)
+            .unwrap()
+            .address,
+        new_charity.address
+    );
+    assert!(test_data.contract_client.try_pending_royalties().is_err());
+
+    test_data.contract_client.freeze_royalties();
+    assert!(test_data.contract_client.royalties_frozen());
+
+    let frozen_error = test_data
+        .contract_client
+        .try_propose_royalties(&new_royalties)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(frozen_error, SCRoyaltyErrors::RoyaltiesAreFrozen.into());
+}
+
+#[test]
+pub fn test_item_royalties_override() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let collaborator: Royalty = Royalty {
+        name: String::from_str(&env, "TheCollaborator"),
+        address: Address::generate(&env),
+        primary_percentage: 500000,
+        secondary_percentage: 500000,
+    };
+    test_data
+        .contract_client
+        .propose_item_royalties(&5, &Some(vec![&env, collaborator.clone()]));
+
+    // Item royalties follow the same timelock as the collection royalties
+    let timelock_error = test_data
+        .contract_client
+        .try_apply_item_royalties(&5)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        timelock_error,
+        SCRoyaltyErrors::RoyaltiesTimelockIsActive.into()
+    );
+    assert_eq!(test_data.contract_client.item_royalties(&5).len(), 3);
+    assert_eq!(
+        test_data
+            .contract_client
+            .pending_item_royalties(&5)
+            .apply_ledger,
+        DEFAULT_ROYALTIES_DELAY
+    );
+
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = DEFAULT_ROYALTIES_DELAY);
+    test_data.contract_client.apply_item_royalties(&5);
+
+    assert_eq!(test_data.contract_client.item_royalties(&5).len(), 1);
+    assert_eq!(test_data.contract_client.item_royalties(&6).len(), 3);
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128 * 2));
+
+    test_data
+        .contract_client
+        .buy_many(&buyer, &vec![&env, 5, 6], &u128::MAX, &None);
+
+    let share: i128 = test_data.initial_price as i128 / 100;
+    assert_eq!(
+        test_data.usd_token_client.balance(&collaborator.address),
+        share * 5
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.platform_royalty.address),
+        share
+    );
+
+    // A bundle pays the royalties of its own Items
+    let bundle_id: u64 =
+        test_data
+            .contract_client
+            .list_bundle(&buyer, &vec![&env, 5], &100_0000000);
+    let bundle_buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&bundle_buyer, &100_0000000);
+    test_data
+        .contract_client
+        .buy_bundle(&bundle_buyer, &bundle_id, &100_0000000);
+
+    assert_eq!(
+        test_data.usd_token_client.balance(&collaborator.address),
+        share * 5 + 5_0000000
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.platform_royalty.address),
+        share
+    );
+
+    test_data.contract_client.propose_item_royalties(&5, &None);
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = DEFAULT_ROYALTIES_DELAY * 2);
+    test_data.contract_client.apply_item_royalties(&5);
+    assert_eq!(test_data.contract_client.item_royalties(&5).len(), 3);
+
+    let no_proposal_error = test_data
+        .contract_client
+        .try_apply_item_royalties(&5)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        no_proposal_error,
+        SCRoyaltyErrors::PendingRoyaltiesDoNotExist.into()
+    );
+
+    test_data.contract_client.freeze_royalties();
+    let frozen_error = test_data
+        .contract_client
+        .try_propose_item_royalties(&5, &Some(vec![&env, collaborator]))
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(frozen_error, SCRoyaltyErrors::RoyaltiesAreFrozen.into());
+}
+
+#[test]
+pub fn test_royalty_info() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    // Before the Item is minted the quote includes the first sale royalties
+    let first_sale_info = test_data
+        .contract_client
+        .royalty_info(&0, &test_data.initial_price);
+
+    assert_eq!(first_sale_info.len(), 3);
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128));
+    test_data
+        .contract_client
+        .buy(&buyer, &0, &test_data.initial_price, &None);
+
+    for (address, amount) in first_sale_info.iter() {
+        assert_eq!(test_data.usd_token_client.balance(&address), amount as i128);
+    }
+
+    let secondary_sale_info = test_data.contract_client.royalty_info(&0, &1_0000001);
+
+    assert_eq!(
+        secondary_sale_info,
+        vec![
+            &env,
+            (test_data.creator_royalty.address.clone(), 300000),
+            (test_data.charity_royalty.address.clone(), 200000),
+        ]
+    );
+}
+
+#[test]
+pub fn test_primary_and_secondary_percentages() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let mut test_data: TestData = create_test_data(&env);
+    test_data.charity_royalty.primary_percentage = 1000000;
+    test_data.charity_royalty.secondary_percentage = 200000;
+    test_data.default_royalties = vec![&env, test_data.charity_royalty.clone()];
+    init_with_test_data(&test_data);
+
+    let buyer: Address = Address::generate(&env);
+    let second_buyer: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&buyer, &100_0000000);
+    test_data
+        .usd_token_admin_client
+        .mint(&second_buyer, &100_0000000);
+
+    test_data
+        .contract_client
+        .set_price_override(&0, &Some(100_0000000));
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.charity_royalty.address),
+        10_0000000
+    );
+
+    test_data
+        .contract_client
+        .sell(&0, &100_0000000, &None, &None);
+    test_data
+        .contract_client
+        .buy(&second_buyer, &0, &u128::MAX, &None);
+
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.charity_royalty.address),
+        12_0000000
+    );
+}
+
+#[test]
+pub fn test_migrate_legacy_royalties() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let already_migrated_error = test_data
+        .contract_client
+        .try_migrate_royalties()
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        already_migrated_error,
+        SCRoyaltyErrors::RoyaltiesAreAlreadyMigrated.into()
+    );
+
+    // A collectible created before the royalties had separate percentages
+    let legacy_royalties: Vec<LegacyRoyalty> = vec![
+        &env,
+        LegacyRoyalty {
+            address: test_data.platform_royalty.address.clone(),
+            first_sale: true,
+            name: test_data.platform_royalty.name.clone(),
+            percentage: test_data.platform_royalty.primary_percentage,
+        },
+        LegacyRoyalty {
+            address: test_data.creator_royalty.address.clone(),
+            first_sale: false,
+            name: test_data.creator_royalty.name.clone(),
+            percentage: test_data.creator_royalty.primary_percentage,
+        },
+    ];
+    env.as_contract(&test_data.contract_client.address, || {
+        env.storage()
+            .persistent()
+            .set(&RoyaltiesDataKeys::Royalties, &legacy_royalties);
+        env.storage()
+            .instance()
+            .remove(&RoyaltiesDataKeys::RoyaltiesVersion);
+    });
+
+    let not_migrated_error = test_data
+        .contract_client
+        .try_royalties()
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        not_migrated_error,
+        SCRoyaltyErrors::RoyaltiesAreNotMigrated.into()
+    );
+
+    test_data.contract_client.migrate_royalties();
+
+    // The "first_sale" royalty is only paid on the first sale, the rest keep their percentage on every sale
+    let royalties: Vec<Royalty> = test_data.contract_client.royalties();
+    assert_eq!(royalties.len(), 2);
+    assert_eq!(
+        royalties.get(0).unwrap().primary_percentage,
+        test_data.platform_royalty.primary_percentage
+    );
+    assert_eq!(royalties.get(0).unwrap().secondary_percentage, 0);
+    assert_eq!(
+        royalties.get(1).unwrap().secondary_percentage,
+        test_data.creator_royalty.primary_percentage
+    );
+}
+
+/// The CoreData saved by the first version of the contract
+#[contracttype]
+pub struct BaselineCoreData {
+    pub admin: Address,
+    pub supply: u64,
+    pub initial_price: u128,
+    pub collection_currency: Address,
+    pub initial_seller: Address,
+}
+
+/// The Item saved by the first version of the contract
+#[contracttype]
+pub struct BaselineItem {
+    pub number: u64,
+    pub owner: Address,
+    pub for_sale: bool,
+    pub price: u128,
+}
+
+#[test]
+pub fn test_migrate_baseline_collectible() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let seller: Address = Address::generate(&env);
+    test_data.contract_client.mint(&7, &seller);
+
+    // The storage of a collectible created by the first version of the contract, the legacy royalties have values the
+    // current validation rejects: a zero percentage and a duplicated address
+    let legacy_royalties: Vec<LegacyRoyalty> = vec![
+        &env,
+        LegacyRoyalty {
+            address: test_data.platform_royalty.address.clone(),
+            first_sale: true,
+            name: test_data.platform_royalty.name.clone(),
+            percentage: test_data.platform_royalty.primary_percentage,
+        },
+        LegacyRoyalty {
+            address: test_data.creator_royalty.address.clone(),
+            first_sale: false,
+            name: test_data.creator_royalty.name.clone(),
+            percentage: test_data.creator_royalty.primary_percentage,
+        },
+        LegacyRoyalty {
+            address: test_data.creator_royalty.address.clone(),
+            first_sale: false,
+            name: test_data.charity_royalty.name.clone(),
+            percentage: 0,
+        },
+    ];
+    env.as_contract(&test_data.contract_client.address, || {
+        env.storage().instance().set(
+            &CoreDataKeys::CoreData,
+            &BaselineCoreData {
+                admin: test_data.admin.clone(),
+                supply: test_data.supply,
+                initial_price: test_data.initial_price,
+                collection_currency: test_data.usd_token_client.address.clone(),
+                initial_seller: test_data.initial_seller.clone(),
+            },
+        );
+        env.storage().persistent().set(
+            &ItemsDataKeys::Item(7),
+            &BaselineItem {
+                number: 7,
+                owner: seller.clone(),
+                for_sale: true,
+                price: 100_0000000,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&RoyaltiesDataKeys::Royalties, &legacy_royalties);
+        env.storage()
+            .instance()
+            .remove(&RoyaltiesDataKeys::RoyaltiesVersion);
+        env.storage().instance().remove(&ItemsDataKeys::MintedCount);
+    });
+
+    test_data.contract_client.seed_minted_count(&1);
+    assert_eq!(test_data.contract_client.minted(), 1);
+
+    // Sales can't pay the royalties until they are migrated
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(100_0000000 + test_data.initial_price as i128));
+
+    let not_migrated_error = test_data
+        .contract_client
+        .try_buy(&buyer, &7, &100_0000000, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        not_migrated_error,
+        SCRoyaltyErrors::RoyaltiesAreNotMigrated.into()
+    );
+
+    test_data.contract_client.migrate_royalties();
+    assert_eq!(test_data.contract_client.royalties().len(), 3);
+
+    // The Item listed before the upgrade can still be bought
+    test_data
+        .contract_client
+        .buy(&buyer, &7, &100_0000000, &None);
+
+    assert_eq!(test_data.contract_client.item(&7).owner, buyer);
+    assert_eq!(test_data.usd_token_client.balance(&seller), 97_0000000);
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        3_0000000
+    );
+
+    // And so can the Items that haven't been minted yet
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+    assert_eq!(test_data.contract_client.item(&0).owner, buyer);
+    assert_eq!(test_data.contract_client.minted(), 2);
+}
@@ -0,0 +1,886 @@
+#![cfg(test)]
+
+use crate::bumps::ESCROWED_SALES_BUMP_CONSTANT;
+use crate::errors::SCErrors;
+use crate::storage::core::PrimarySale;
+use crate::storage::items::{Item, ItemsDataKeys};
+use crate::storage::sales::{
+    Allowlist, BondingCurveKind, PriceTier, ReservedItems, SalePhase, SalesDataKeys,
+};
+use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
+use crate::utils::royalties::DEFAULT_ROYALTIES_DELAY;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::xdr::{LedgerKey, ScVal, ToXdr};
+use soroban_sdk::{vec, Address, Bytes, BytesN, Env, IntoVal, TryFromVal, Val, Vec};
+
+fn leaf(env: &Env, address: &Address) -> BytesN<32> {
+    env.crypto().sha256(&address.clone().to_xdr(env))
+}
+
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let mut data: Bytes = Bytes::new(env);
+    if a < b {
+        data.append(&a.clone().into());
+        data.append(&b.clone().into());
+    } else {
+        data.append(&b.clone().into());
+        data.append(&a.clone().into());
+    }
+    env.crypto().sha256(&data)
+}
+
+#[test]
+pub fn test_dutch_auction_primary_price() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    assert_eq!(
+        test_data.contract_client.primary_price(&0),
+        test_data.initial_price
+    );
+
+    let invalid_schedule_error = test_data
+        .contract_client
+        .try_set_dutch_auction(&10_0000000, &20_0000000, &100, &1_0000000)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        invalid_schedule_error,
+        SCErrors::InvalidPriceSchedule.into()
+    );
+
+    test_data
+        .contract_client
+        .set_dutch_auction(&100_0000000, &20_0000000, &100, &1_0000000);
+
+    assert_eq!(test_data.contract_client.primary_price(&0), 100_0000000);
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 130);
+    assert_eq!(test_data.contract_client.primary_price(&0), 70_0000000);
+
+    let buyer: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&buyer, &70_0000000);
+    test_data
+        .contract_client
+        .buy(&buyer, &0, &70_0000000, &None);
+
+    assert_eq!(test_data.usd_token_client.balance(&buyer), 0);
+
+    // The price never goes below the floor price
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = 1000);
+    assert_eq!(test_data.contract_client.primary_price(&0), 20_0000000);
+
+    test_data.contract_client.remove_dutch_auction();
+    assert_eq!(
+        test_data.contract_client.primary_price(&0),
+        test_data.initial_price
+    );
+}
+
+#[test]
+pub fn test_sale_phases_with_allowlist() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let supporter: Address = Address::generate(&env);
+    let charity: Address = Address::generate(&env);
+    let partner: Address = Address::generate(&env);
+    let outsider: Address = Address::generate(&env);
+
+    let supporter_leaf: BytesN<32> = leaf(&env, &supporter);
+    let charity_leaf: BytesN<32> = leaf(&env, &charity);
+    let partner_leaf: BytesN<32> = leaf(&env, &partner);
+    let first_node: BytesN<32> = hash_pair(&env, &supporter_leaf, &charity_leaf);
+    let root: BytesN<32> = hash_pair(&env, &first_node, &partner_leaf);
+
+    let unsorted_phases_error = test_data
+        .contract_client
+        .try_set_sale_phases(&vec![
+            &env,
+            SalePhase {
+                start_ledger: 200,
+                price: Some(15_0000000),
+                allowlist: Allowlist::Public,
+                max_per_wallet: None,
+            },
+            SalePhase {
+                start_ledger: 100,
+                price: Some(10_0000000),
+                allowlist: Allowlist::MerkleRoot(root.clone()),
+                max_per_wallet: None,
+            },
+        ])
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(unsorted_phases_error, SCErrors::InvalidSalePhases.into());
+
+    test_data.contract_client.set_sale_phases(&vec![
+        &env,
+        SalePhase {
+            start_ledger: 100,
+            price: Some(10_0000000),
+            allowlist: Allowlist::MerkleRoot(root.clone()),
+            max_per_wallet: None,
+        },
+        SalePhase {
+            start_ledger: 200,
+            price: Some(15_0000000),
+            allowlist: Allowlist::Public,
+            max_per_wallet: None,
+        },
+    ]);
+
+    for buyer in [&supporter, &partner, &outsider] {
+        test_data.usd_token_admin_client.mint(buyer, &15_0000000);
+    }
+
+    let not_open_error = test_data
+        .contract_client
+        .try_buy(&supporter, &0, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_open_error, SCErrors::PrimarySaleIsNotOpen.into());
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 100);
+    assert_eq!(test_data.contract_client.primary_price(&0), 10_0000000);
+
+    let not_allowed_error = test_data
+        .contract_client
+        .try_buy(
+            &outsider,
+            &0,
+            &u128::MAX,
+            &Some(vec![&env, charity_leaf.clone(), partner_leaf.clone()]),
+        )
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_allowed_error, SCErrors::AddressIsNotAllowed.into());
+
+    test_data.contract_client.buy(
+        &supporter,
+        &0,
+        &10_0000000,
+        &Some(vec![&env, charity_leaf.clone(), partner_leaf.clone()]),
+    );
+    test_data
+        .contract_client
+        .buy(&partner, &1, &10_0000000, &Some(vec![&env, first_node]));
+
+    assert_eq!(test_data.contract_client.item(&0).owner, supporter);
+    assert_eq!(test_data.contract_client.item(&1).owner, partner);
+
+    // Once the public phase starts anyone can buy without a proof
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 200);
+    test_data
+        .contract_client
+        .buy(&outsider, &2, &15_0000000, &None);
+
+    assert_eq!(test_data.contract_client.item(&2).owner, outsider);
+    assert_eq!(test_data.usd_token_client.balance(&outsider), 0);
+}
+
+#[test]
+pub fn test_max_per_wallet() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let buyer: Address = Address::generate(&env);
+    let friend: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128 * 3));
+
+    test_data.contract_client.set_max_per_wallet(&Some(2));
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+    test_data.contract_client.buy(&buyer, &1, &u128::MAX, &None);
+
+    // Transferring the Items away doesn't reset the count
+    test_data.contract_client.transfer(&0, &friend);
+    test_data.contract_client.transfer(&1, &friend);
+    assert_eq!(test_data.contract_client.primary_purchases(&buyer), 2);
+
+    let limit_error = test_data
+        .contract_client
+        .try_buy(&buyer, &2, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(limit_error, SCErrors::WalletLimitReached.into());
+
+    // The limit of the active phase takes precedence
+    test_data.contract_client.set_sale_phases(&vec![
+        &env,
+        SalePhase {
+            start_ledger: 0,
+            price: Some(test_data.initial_price),
+            allowlist: Allowlist::Public,
+            max_per_wallet: Some(3),
+        },
+    ]);
+    test_data.contract_client.buy(&buyer, &2, &u128::MAX, &None);
+
+    assert_eq!(test_data.contract_client.item(&2).owner, buyer);
+    assert_eq!(test_data.contract_client.primary_purchases(&buyer), 3);
+}
+
+#[test]
+pub fn test_primary_sale_window_and_pause() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let buyer: Address = Address::generate(&env);
+    let second_buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128 * 3));
+
+    let invalid_window_error = test_data
+        .contract_client
+        .try_set_primary_sale_window(&200, &100)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(invalid_window_error, SCErrors::InvalidSaleWindow.into());
+
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+    test_data
+        .contract_client
+        .set_primary_sale_window(&100, &200);
+
+    let not_started_error = test_data
+        .contract_client
+        .try_buy(&buyer, &1, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_started_error, SCErrors::PrimarySaleIsNotOpen.into());
+
+    // Secondary sales are not affected by the window
+    test_data.contract_client.sell(&0, &1_0000000, &None, &None);
+    test_data
+        .usd_token_admin_client
+        .mint(&second_buyer, &1_0000000);
+    test_data
+        .contract_client
+        .buy(&second_buyer, &0, &u128::MAX, &None);
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 100);
+    test_data.contract_client.pause_primary_sale();
+
+    let primary_sale: PrimarySale = test_data.contract_client.primary_sale();
+    assert_eq!(&primary_sale.start_ledger, &100);
+    assert_eq!(&primary_sale.end_ledger, &200);
+    assert!(primary_sale.paused);
+
+    let paused_error = test_data
+        .contract_client
+        .try_buy(&buyer, &1, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(paused_error, SCErrors::PrimarySaleIsNotOpen.into());
+
+    test_data.contract_client.resume_primary_sale();
+    test_data.contract_client.buy(&buyer, &1, &u128::MAX, &None);
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 201);
+    let ended_error = test_data
+        .contract_client
+        .try_buy(&buyer, &2, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(ended_error, SCErrors::PrimarySaleIsNotOpen.into());
+}
+
+#[test]
+pub fn test_price_tiers_and_overrides() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let overlapping_tiers_error = test_data
+        .contract_client
+        .try_set_price_tiers(&vec![
+            &env,
+            PriceTier {
+                from: 0,
+                to: 10,
+                price: 50_0000000,
+            },
+            PriceTier {
+                from: 10,
+                to: 19,
+                price: 30_0000000,
+            },
+        ])
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(overlapping_tiers_error, SCErrors::InvalidPriceTiers.into());
+
+    test_data.contract_client.set_price_tiers(&vec![
+        &env,
+        PriceTier {
+            from: 0,
+            to: 9,
+            price: 50_0000000,
+        },
+        PriceTier {
+            from: 10,
+            to: 19,
+            price: 30_0000000,
+        },
+    ]);
+    test_data
+        .contract_client
+        .set_price_override(&5, &Some(100_0000000));
+
+    assert_eq!(test_data.contract_client.primary_price(&0), 50_0000000);
+    assert_eq!(test_data.contract_client.primary_price(&5), 100_0000000);
+    assert_eq!(test_data.contract_client.primary_price(&12), 30_0000000);
+    assert_eq!(
+        test_data.contract_client.primary_price(&20),
+        test_data.initial_price
+    );
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(130_0000000 + test_data.initial_price as i128));
+
+    test_data.contract_client.buy(&buyer, &5, &u128::MAX, &None);
+    test_data
+        .contract_client
+        .buy(&buyer, &12, &u128::MAX, &None);
+    test_data
+        .contract_client
+        .buy(&buyer, &20, &u128::MAX, &None);
+
+    assert_eq!(test_data.usd_token_client.balance(&buyer), 0);
+
+    test_data.contract_client.set_price_override(&6, &None);
+    assert_eq!(test_data.contract_client.primary_price(&6), 50_0000000);
+}
+
+#[test]
+pub fn test_sale_phase_price_over_price_tiers() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    test_data.contract_client.set_price_tiers(&vec![
+        &env,
+        PriceTier {
+            from: 0,
+            to: 9,
+            price: 50_0000000,
+        },
+    ]);
+    test_data
+        .contract_client
+        .set_price_override(&5, &Some(100_0000000));
+    test_data.contract_client.set_sale_phases(&vec![
+        &env,
+        SalePhase {
+            start_ledger: 100,
+            price: Some(15_0000000),
+            allowlist: Allowlist::Public,
+            max_per_wallet: None,
+        },
+    ]);
+
+    // Before the phase starts the primary sale is closed but the tiers still define the price
+    assert_eq!(test_data.contract_client.primary_price(&0), 50_0000000);
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 100);
+
+    assert_eq!(test_data.contract_client.primary_price(&0), 15_0000000);
+    assert_eq!(test_data.contract_client.primary_price(&5), 100_0000000);
+    assert_eq!(test_data.contract_client.primary_price(&20), 15_0000000);
+
+    let buyer: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&buyer, &15_0000000);
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+
+    assert_eq!(test_data.usd_token_client.balance(&buyer), 0);
+}
+
+#[test]
+pub fn test_reserved_items() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let mut test_data: TestData = create_test_data(&env);
+    test_data.supply = 3;
+    init_with_test_data(&test_data);
+
+    let buyer: Address = Address::generate(&env);
+    let artist: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128 * 2));
+
+    let reserved_items = vec![&env, ReservedItems { from: 0, to: 2 }];
+
+    let unauthorized_error = test_data
+        .contract_client
+        .try_set_reserved_items(&buyer, &reserved_items)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(unauthorized_error, SCErrors::UnauthorizedCaller.into());
+
+    test_data
+        .contract_client
+        .set_reserved_items(&test_data.initial_seller, &reserved_items);
+
+    let reserved_error = test_data
+        .contract_client
+        .try_buy(&buyer, &1, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(reserved_error, SCErrors::ItemIsNotForSale.into());
+
+    // Random purchases skip the reserved Items
+    assert_eq!(
+        test_data
+            .contract_client
+            .buy_random(&buyer, &u128::MAX, &None),
+        3
+    );
+
+    let only_reserved_error = test_data
+        .contract_client
+        .try_buy_random(&buyer, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(only_reserved_error, SCErrors::ItemIsNotForSale.into());
+
+    // Reserved Items can still be minted
+    test_data.contract_client.mint(&1, &artist);
+    assert_eq!(test_data.contract_client.item(&1).owner, artist);
+
+    // Once the reservations are removed the Items left go back to the random purchases
+    test_data
+        .contract_client
+        .set_reserved_items(&test_data.initial_seller, &vec![&env]);
+
+    let picked: u64 = test_data
+        .contract_client
+        .buy_random(&buyer, &u128::MAX, &None);
+    assert!(picked == 0 || picked == 2);
+}
+
+#[test]
+pub fn test_reserve_large_range() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let mut test_data: TestData = create_test_data(&env);
+    test_data.supply = 1_000_000;
+    init_with_test_data(&test_data);
+
+    // Only the ranges are stored so the size of the range doesn't change the cost of the call
+    let reserved_items = vec![
+        &env,
+        ReservedItems {
+            from: 0,
+            to: 999_999,
+        },
+    ];
+    test_data
+        .contract_client
+        .set_reserved_items(&test_data.admin, &reserved_items);
+
+    assert_eq!(test_data.contract_client.reserved_items(), reserved_items);
+
+    let buyer: Address = Address::generate(&env);
+    let reserved_error = test_data
+        .contract_client
+        .try_buy(&buyer, &500_000, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(reserved_error, SCErrors::ItemIsNotForSale.into());
+}
+
+#[test]
+pub fn test_bonding_curve() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let quote_error = test_data
+        .contract_client
+        .try_curve_quote(&1)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(quote_error, SCErrors::BondingCurveDoesNotExist.into());
+
+    test_data
+        .contract_client
+        .set_bonding_curve(&BondingCurveKind::Linear, &10_0000000, &1_0000000);
+
+    assert_eq!(test_data.contract_client.curve_quote(&3), 33_0000000);
+
+    let buyer: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&buyer, &21_0000000);
+
+    test_data
+        .contract_client
+        .buy(&buyer, &0, &10_0000000, &None);
+    assert_eq!(test_data.contract_client.minted(), 1);
+
+    // The price went up after the first purchase
+    let max_price_error = test_data
+        .contract_client
+        .try_buy(&buyer, &1, &10_0000000, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(max_price_error, SCErrors::PriceIsHigherThanMaxPrice.into());
+
+    test_data
+        .contract_client
+        .buy(&buyer, &1, &11_0000000, &None);
+    assert_eq!(test_data.usd_token_client.balance(&buyer), 0);
+
+    test_data.contract_client.set_bonding_curve(
+        &BondingCurveKind::Exponential,
+        &100_0000000,
+        &1000000,
+    );
+
+    assert_eq!(test_data.contract_client.primary_price(&2), 121_0000000);
+    assert_eq!(test_data.contract_client.curve_quote(&2), 254_1000000);
+
+    // Items minted by the initial_seller also move the curve
+    test_data
+        .contract_client
+        .mint(&2, &test_data.initial_seller);
+    assert_eq!(test_data.contract_client.primary_price(&3), 133_1000000);
+
+    test_data.contract_client.set_bonding_curve(
+        &BondingCurveKind::Exponential,
+        &(u128::MAX / 4),
+        &1_0000000,
+    );
+
+    let overflow_error = test_data
+        .contract_client
+        .try_primary_price(&3)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(overflow_error, SCErrors::BondingCurvePriceOverflow.into());
+
+    test_data.contract_client.remove_bonding_curve();
+    assert_eq!(
+        test_data.contract_client.primary_price(&3),
+        test_data.initial_price
+    );
+}
+
+#[test]
+pub fn test_price_schedules_do_not_shadow_each_other() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    test_data
+        .contract_client
+        .set_bonding_curve(&BondingCurveKind::Linear, &10_0000000, &1_0000000);
+
+    // A phase without its own price keeps the bonding curve
+    test_data.contract_client.set_sale_phases(&vec![
+        &env,
+        SalePhase {
+            start_ledger: 0,
+            price: None,
+            allowlist: Allowlist::Public,
+            max_per_wallet: None,
+        },
+    ]);
+    assert_eq!(test_data.contract_client.primary_price(&0), 10_0000000);
+
+    // The Dutch auction replaces the bonding curve
+    test_data
+        .contract_client
+        .set_dutch_auction(&50_0000000, &20_0000000, &0, &1_0000000);
+    assert_eq!(test_data.contract_client.primary_price(&0), 50_0000000);
+
+    let quote_error = test_data
+        .contract_client
+        .try_curve_quote(&1)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(quote_error, SCErrors::BondingCurveDoesNotExist.into());
+
+    // And the bonding curve replaces the Dutch auction
+    test_data
+        .contract_client
+        .set_bonding_curve(&BondingCurveKind::Linear, &10_0000000, &1_0000000);
+    test_data.contract_client.remove_bonding_curve();
+    assert_eq!(
+        test_data.contract_client.primary_price(&0),
+        test_data.initial_price
+    );
+}
+
+#[test]
+pub fn test_seed_minted_count() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let already_initialized_error = test_data
+        .contract_client
+        .try_seed_minted_count(&10)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        already_initialized_error,
+        SCErrors::AlreadyInitialized.into()
+    );
+
+    // Contracts initialized before the minted count existed don't have it
+    env.as_contract(&test_data.contract_client.address, || {
+        env.storage().instance().remove(&ItemsDataKeys::MintedCount);
+    });
+
+    let invalid_count_error = test_data
+        .contract_client
+        .try_seed_minted_count(&(test_data.supply + 2))
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(invalid_count_error, SCErrors::InvalidAmount.into());
+
+    test_data.contract_client.seed_minted_count(&10);
+    assert_eq!(test_data.contract_client.minted(), 10);
+
+    test_data
+        .contract_client
+        .set_bonding_curve(&BondingCurveKind::Linear, &10_0000000, &1_0000000);
+    assert_eq!(test_data.contract_client.primary_price(&0), 20_0000000);
+}
+
+#[test]
+pub fn test_prune_unminted_items() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let mut test_data: TestData = create_test_data(&env);
+    test_data.supply = 99;
+    init_with_test_data(&test_data);
+
+    // Items minted before the upgrade were never removed from the unminted list
+    let owner: Address = Address::generate(&env);
+    let mut minted: Vec<u64> = Vec::new(&env);
+    env.as_contract(&test_data.contract_client.address, || {
+        for number in 0..=test_data.supply {
+            if number != 42 {
+                env.storage().persistent().set(
+                    &ItemsDataKeys::Item(number),
+                    &Item {
+                        number,
+                        for_sale: false,
+                        owner: owner.clone(),
+                        price: 0,
+                    },
+                );
+                minted.push_back(number);
+            }
+        }
+    });
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128));
+
+    // The draws are capped so the random purchase fails instead of walking the whole list
+    let too_many_draws_error = test_data
+        .contract_client
+        .try_buy_random(&buyer, &u128::MAX, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(too_many_draws_error, SCErrors::ItemIsNotForSale.into());
+
+    test_data.contract_client.prune_unminted_items(&minted);
+    assert_eq!(
+        test_data
+            .contract_client
+            .buy_random(&buyer, &u128::MAX, &None),
+        42
+    );
+}
+
+#[test]
+pub fn test_refund_window() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    // The entries must outlive the royalties timelock used below
+    env.ledger().with_mut(|ledger| {
+        ledger.min_persistent_entry_ttl = DEFAULT_ROYALTIES_DELAY * 2;
+        ledger.min_temp_entry_ttl = DEFAULT_ROYALTIES_DELAY * 2;
+    });
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    test_data.contract_client.set_refund_window(&100);
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128 * 2));
+
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+    test_data.contract_client.buy(&buyer, &1, &u128::MAX, &None);
+
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.contract_client.address),
+        test_data.initial_price as i128 * 2
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.initial_seller),
+        0
+    );
+
+    test_data.contract_client.refund(&buyer, &0);
+
+    assert!(test_data.contract_client.try_item(&0).is_err());
+    assert_eq!(test_data.contract_client.balance(&buyer), 1);
+    assert_eq!(
+        test_data.usd_token_client.balance(&buyer),
+        test_data.initial_price as i128
+    );
+
+    let window_open_error = test_data
+        .contract_client
+        .try_release_proceeds(&vec![&env, 1])
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(window_open_error, SCErrors::RefundWindowIsOpen.into());
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 101);
+
+    let window_closed_error = test_data
+        .contract_client
+        .try_refund(&buyer, &1)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(window_closed_error, SCErrors::RefundWindowHasClosed.into());
+
+    // Royalties updated after the purchase don't change how the escrowed payment is split
+    test_data
+        .contract_client
+        .propose_royalties(&vec![&env, test_data.platform_royalty.clone()]);
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = 101 + DEFAULT_ROYALTIES_DELAY);
+    test_data.contract_client.apply_royalties();
+
+    test_data.contract_client.release_proceeds(&vec![&env, 1]);
+
+    let platform_share: i128 = test_data.initial_price as i128 / 100;
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.initial_seller),
+        test_data.initial_price as i128 * 94 / 100
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.platform_royalty.address),
+        platform_share
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.contract_client.address),
+        0
+    );
+
+    // The refunded Item is back in the primary sale
+    test_data.contract_client.set_refund_window(&0);
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+    assert_eq!(test_data.contract_client.item(&0).owner, buyer);
+}
+
+#[test]
+pub fn test_escrowed_sale_outlives_refund_window() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let refund_window: u32 = ESCROWED_SALES_BUMP_CONSTANT * 2;
+    test_data.contract_client.set_refund_window(&refund_window);
+
+    let buyer: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&buyer, &(test_data.initial_price as i128));
+    test_data.contract_client.buy(&buyer, &0, &u128::MAX, &None);
+
+    let escrowed_sale_key: ScVal = env.as_contract(&test_data.contract_client.address, || {
+        let key: Val = SalesDataKeys::EscrowedSale(0).into_val(&env);
+        ScVal::try_from_val(&env, &key).unwrap()
+    });
+    let live_until: u32 = env
+        .to_snapshot()
+        .ledger
+        .ledger_entries
+        .iter()
+        .find_map(|(key, (_, live_until))| match key.as_ref() {
+            LedgerKey::ContractData(data) if data.key == escrowed_sale_key => *live_until,
+            _ => None,
+        })
+        .unwrap();
+
+    assert!(live_until >= env.ledger().sequence() + refund_window);
+}
@@ -4,7 +4,7 @@ use crate::errors::SCErrors;
 use crate::storage::items::Item;
 use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
 use soroban_sdk::testutils::arbitrary::std;
-use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Ledger};
 use soroban_sdk::{Address, Env, IntoVal, Symbol};
 
 #[test]
@@ -16,7 +16,7 @@ pub fn test_selling_product_doesnt_exist() {
 
     let does_not_exist_error = test_data
         .contract_client
-        .try_sell(&0, &100_0000000)
+        .try_sell(&0, &100_0000000, &None, &None)
         .unwrap_err()
         .unwrap();
 
@@ -36,7 +36,9 @@ pub fn test_sell_logic() {
         .usd_token_admin_client
         .mint(&owner, &(test_data.initial_price as i128));
 
-    test_data.contract_client.buy(&owner, &0);
+    test_data
+        .contract_client
+        .buy(&owner, &0, &test_data.initial_price, &None);
 
     let mut item: Item = test_data.contract_client.item(&0);
 
@@ -45,7 +47,9 @@ pub fn test_sell_logic() {
     assert_eq!(&item.for_sale, &false);
     assert_eq!(&item.number, &0);
 
-    test_data.contract_client.sell(&0, &50_0000000);
+    test_data
+        .contract_client
+        .sell(&0, &50_0000000, &None, &None);
 
     assert_eq!(
         env.auths().first().unwrap(),
@@ -55,7 +59,7 @@ pub fn test_sell_logic() {
                 function: AuthorizedFunction::Contract((
                     test_data.contract_client.address.clone(),
                     Symbol::new(&env, "sell"),
-                    (0u64, 50_0000000u128,).into_val(&env)
+                    (0u64, 50_0000000u128, None::<u32>, None::<Address>).into_val(&env)
                 )),
                 sub_invocations: std::vec![],
             }
@@ -68,7 +72,7 @@ pub fn test_sell_logic() {
     assert_eq!(&item.for_sale, &true);
     assert_eq!(&item.number, &0);
 
-    test_data.contract_client.sell(&0, &0);
+    test_data.contract_client.sell(&0, &0, &None, &None);
 
     item = test_data.contract_client.item(&0);
     assert_eq!(&item.owner, &owner);
@@ -76,3 +80,63 @@ pub fn test_sell_logic() {
     assert_eq!(&item.for_sale, &false);
     assert_eq!(&item.number, &0);
 }
+
+#[test]
+pub fn test_sell_with_expiration() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let owner: Address = Address::generate(&env);
+    test_data.contract_client.mint(&0, &owner);
+
+    let invalid_expiration_error = test_data
+        .contract_client
+        .try_sell(&0, &50_0000000, &Some(0), &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        invalid_expiration_error,
+        SCErrors::InvalidExpirationLedger.into()
+    );
+
+    test_data
+        .contract_client
+        .sell(&0, &50_0000000, &Some(100), &None);
+
+    let item: Item = test_data.contract_client.item(&0);
+    assert_eq!(&item.for_sale, &true);
+    assert_eq!(
+        &test_data.contract_client.listing(&0).expiration_ledger,
+        &Some(100)
+    );
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 101);
+
+    // The Item is not shown as for sale once the listing expires
+    let item: Item = test_data.contract_client.item(&0);
+    assert_eq!(&item.for_sale, &false);
+
+    let buyer: Address = Address::generate(&env);
+    test_data.usd_token_admin_client.mint(&buyer, &50_0000000);
+
+    let expired_error = test_data
+        .contract_client
+        .try_buy(&buyer, &0, &50_0000000, &None)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(expired_error, SCErrors::ListingHasExpired.into());
+
+    // Cancelling the offer also removes the expiration
+    test_data.contract_client.sell(&0, &0, &Some(200), &None);
+
+    let item: Item = test_data.contract_client.item(&0);
+    assert_eq!(&item.for_sale, &false);
+    assert_eq!(
+        &test_data.contract_client.listing(&0).expiration_ledger,
+        &None
+    );
+}
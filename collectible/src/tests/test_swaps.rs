@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use crate::errors::SCErrors;
+use crate::storage::swaps::SwapRoyaltyPolicy;
+use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, Env};
+
+#[test]
+pub fn test_swap_items() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let proposer: Address = Address::generate(&env);
+    let counterparty: Address = Address::generate(&env);
+    test_data.contract_client.mint(&1, &proposer);
+    test_data.contract_client.mint(&2, &proposer);
+    test_data.contract_client.mint(&3, &counterparty);
+
+    let not_owned_error = test_data
+        .contract_client
+        .try_propose_swap(
+            &proposer,
+            &vec![&env, 1, 3],
+            &vec![&env, 3],
+            &counterparty,
+            &None,
+        )
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(not_owned_error, SCErrors::ItemIsNotOwned.into());
+
+    let swap_id: u64 = test_data.contract_client.propose_swap(
+        &proposer,
+        &vec![&env, 1, 2],
+        &vec![&env, 3],
+        &counterparty,
+        &None,
+    );
+
+    test_data.contract_client.accept_swap(&swap_id);
+
+    assert_eq!(test_data.contract_client.item(&1).owner, counterparty);
+    assert_eq!(test_data.contract_client.item(&2).owner, counterparty);
+    assert_eq!(test_data.contract_client.item(&3).owner, proposer);
+    assert_eq!(test_data.contract_client.balance(&proposer), 1);
+    assert_eq!(test_data.contract_client.balance(&counterparty), 2);
+
+    let does_not_exist_error = test_data
+        .contract_client
+        .try_swap(&swap_id)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(does_not_exist_error, SCErrors::SwapDoesNotExist.into());
+}
+
+#[test]
+pub fn test_swap_with_payment_and_royalties() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let proposer: Address = Address::generate(&env);
+    let counterparty: Address = Address::generate(&env);
+    test_data.contract_client.mint(&1, &proposer);
+    test_data.contract_client.mint(&2, &counterparty);
+    test_data
+        .usd_token_admin_client
+        .mint(&proposer, &20_0000000);
+
+    assert_eq!(
+        test_data.contract_client.swap_royalty_policy(),
+        SwapRoyaltyPolicy::NoRoyalties
+    );
+    test_data
+        .contract_client
+        .set_swap_royalty_policy(&SwapRoyaltyPolicy::RoyaltiesOnPayment);
+
+    let cancelled_swap_id: u64 = test_data.contract_client.propose_swap(
+        &proposer,
+        &vec![&env, 1],
+        &vec![&env, 2],
+        &counterparty,
+        &Some(10_0000000),
+    );
+
+    assert_eq!(test_data.usd_token_client.balance(&proposer), 10_0000000);
+
+    test_data.contract_client.cancel_swap(&cancelled_swap_id);
+
+    assert_eq!(test_data.usd_token_client.balance(&proposer), 20_0000000);
+
+    let swap_id: u64 = test_data.contract_client.propose_swap(
+        &proposer,
+        &vec![&env, 1],
+        &vec![&env, 2],
+        &counterparty,
+        &Some(10_0000000),
+    );
+
+    test_data.contract_client.accept_swap(&swap_id);
+
+    assert_eq!(test_data.contract_client.item(&1).owner, counterparty);
+    assert_eq!(test_data.contract_client.item(&2).owner, proposer);
+
+    // The payment pays the second sale royalties (3% + 2%)
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.creator_royalty.address),
+        0_3000000
+    );
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.charity_royalty.address),
+        0_2000000
+    );
+    assert_eq!(
+        test_data.usd_token_client.balance(&counterparty),
+        10_0000000 - 0_3000000 - 0_2000000
+    );
+}
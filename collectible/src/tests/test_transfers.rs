@@ -19,14 +19,18 @@ pub fn test_transferring_ownership() {
         .usd_token_admin_client
         .mint(&buyer, &(test_data.initial_price as i128));
 
-    test_data.contract_client.buy(&buyer, &5);
+    test_data
+        .contract_client
+        .buy(&buyer, &5, &test_data.initial_price, &None);
 
     let mut item: Item = test_data.contract_client.item(&5);
 
     assert_eq!(&item.owner, &buyer);
 
     // Let's just test for the sake of it that if we had it as an offer it will get reset once is transferred
-    test_data.contract_client.sell(&5, &50_0000000);
+    test_data
+        .contract_client
+        .sell(&5, &50_0000000, &None, &None);
 
     let new_owner: Address = Address::generate(&env);
 
@@ -45,20 +45,20 @@ pub fn create_test_data(env: &Env) -> TestData {
     let platform_royalty: Royalty = Royalty {
         name: String::from_str(&env, "ThePlatform"),
         address: Address::generate(&env),
-        first_sale: true,
-        percentage: 0_0100000,
+        primary_percentage: 0_0100000,
+        secondary_percentage: 0,
     };
     let creator_royalty: Royalty = Royalty {
         name: String::from_str(&env, "TheCreator"),
         address: Address::generate(&env),
-        first_sale: false,
-        percentage: 0_0300000,
+        primary_percentage: 0_0300000,
+        secondary_percentage: 0_0300000,
     };
     let charity_royalty: Royalty = Royalty {
         name: String::from_str(&env, "TheCharity"),
         address: Address::generate(&env),
-        first_sale: false,
-        percentage: 0_0200000,
+        primary_percentage: 0_0200000,
+        secondary_percentage: 0_0200000,
     };
 
     default_royalties.push_back(platform_royalty.clone());
@@ -107,9 +107,8 @@ pub fn init_with_test_data(test_data: &TestData) {
         &test_data.initial_price,
         &test_data.initial_seller,
         &test_data.usd_token_client.address,
-        &test_data.token_metadata.name,
-        &test_data.token_metadata.symbol,
-        &test_data.token_metadata.metadata_uri,
+        &test_data.token_metadata,
         &test_data.default_royalties,
+        &None,
     );
 }
@@ -0,0 +1,203 @@
+#![cfg(test)]
+
+use crate::errors::SCErrors;
+use crate::storage::vouchers::Voucher;
+use crate::tests::test_utils::{create_test_data, init_with_test_data, TestData};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, BytesN, Env};
+
+fn sign_voucher(
+    env: &Env,
+    signing_key: &SigningKey,
+    contract: &Address,
+    voucher: &Voucher,
+) -> BytesN<64> {
+    let message: Bytes = (contract.clone(), voucher.clone()).to_xdr(env);
+    let mut buffer: [u8; 1024] = [0; 1024];
+    let length: usize = message.len() as usize;
+    message.copy_into_slice(&mut buffer[..length]);
+
+    BytesN::from_array(env, &signing_key.sign(&buffer[..length]).to_bytes())
+}
+
+#[test]
+pub fn test_redeem_voucher() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let signing_key: SigningKey = SigningKey::from_bytes(&[7u8; 32]);
+    test_data
+        .contract_client
+        .set_voucher_signer(&BytesN::from_array(
+            &env,
+            &signing_key.verifying_key().to_bytes(),
+        ));
+
+    let recipient: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&recipient, &5_0000000);
+
+    let voucher: Voucher = Voucher {
+        item_number: 3,
+        recipient: recipient.clone(),
+        price: 5_0000000,
+        expiration_ledger: 100,
+        nonce: 1,
+    };
+    let signature: BytesN<64> = sign_voucher(
+        &env,
+        &signing_key,
+        &test_data.contract_client.address,
+        &voucher,
+    );
+
+    test_data
+        .contract_client
+        .redeem_voucher(&recipient, &voucher, &signature);
+
+    assert_eq!(test_data.contract_client.item(&3).owner, recipient);
+    assert_eq!(test_data.usd_token_client.balance(&recipient), 0);
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.platform_royalty.address),
+        500000
+    );
+
+    // The same nonce can't be used twice, even for another Item
+    let replayed_voucher: Voucher = Voucher {
+        item_number: 4,
+        price: 0,
+        ..voucher.clone()
+    };
+    let replayed_signature: BytesN<64> = sign_voucher(
+        &env,
+        &signing_key,
+        &test_data.contract_client.address,
+        &replayed_voucher,
+    );
+
+    let replay_error = test_data
+        .contract_client
+        .try_redeem_voucher(&recipient, &replayed_voucher, &replayed_signature)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(replay_error, SCErrors::VoucherWasAlreadyUsed.into());
+
+    let other_recipient: Address = Address::generate(&env);
+    let invalid_recipient_error = test_data
+        .contract_client
+        .try_redeem_voucher(&other_recipient, &replayed_voucher, &replayed_signature)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(invalid_recipient_error, SCErrors::InvalidVoucher.into());
+}
+
+#[test]
+pub fn test_invalid_voucher_signature() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+
+    let signing_key: SigningKey = SigningKey::from_bytes(&[7u8; 32]);
+    let other_key: SigningKey = SigningKey::from_bytes(&[9u8; 32]);
+    let recipient: Address = Address::generate(&env);
+
+    let voucher: Voucher = Voucher {
+        item_number: 3,
+        recipient: recipient.clone(),
+        price: 0,
+        expiration_ledger: 100,
+        nonce: 1,
+    };
+    let forged_signature: BytesN<64> = sign_voucher(
+        &env,
+        &other_key,
+        &test_data.contract_client.address,
+        &voucher,
+    );
+
+    let no_signer_error = test_data
+        .contract_client
+        .try_redeem_voucher(&recipient, &voucher, &forged_signature)
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(no_signer_error, SCErrors::VoucherSignerIsNotDefined.into());
+
+    test_data
+        .contract_client
+        .set_voucher_signer(&BytesN::from_array(
+            &env,
+            &signing_key.verifying_key().to_bytes(),
+        ));
+
+    assert!(test_data
+        .contract_client
+        .try_redeem_voucher(&recipient, &voucher, &forged_signature)
+        .is_err());
+    assert!(test_data.contract_client.try_item(&3).is_err());
+}
+
+#[test]
+pub fn test_redeem_voucher_with_refund_window() {
+    let env: Env = Env::default();
+    env.mock_all_auths();
+
+    let test_data: TestData = create_test_data(&env);
+    init_with_test_data(&test_data);
+    test_data.contract_client.set_refund_window(&100);
+
+    let signing_key: SigningKey = SigningKey::from_bytes(&[7u8; 32]);
+    test_data
+        .contract_client
+        .set_voucher_signer(&BytesN::from_array(
+            &env,
+            &signing_key.verifying_key().to_bytes(),
+        ));
+
+    let recipient: Address = Address::generate(&env);
+    test_data
+        .usd_token_admin_client
+        .mint(&recipient, &5_0000000);
+
+    let voucher: Voucher = Voucher {
+        item_number: 3,
+        recipient: recipient.clone(),
+        price: 5_0000000,
+        expiration_ledger: 100,
+        nonce: 1,
+    };
+    let signature: BytesN<64> = sign_voucher(
+        &env,
+        &signing_key,
+        &test_data.contract_client.address,
+        &voucher,
+    );
+
+    test_data
+        .contract_client
+        .redeem_voucher(&recipient, &voucher, &signature);
+
+    // The payment is kept by the contract like any other primary purchase
+    assert_eq!(
+        test_data
+            .usd_token_client
+            .balance(&test_data.contract_client.address),
+        5_0000000
+    );
+    assert_eq!(test_data.contract_client.escrowed_sale(&3).buyer, recipient);
+
+    test_data.contract_client.refund(&recipient, &3);
+    assert_eq!(test_data.usd_token_client.balance(&recipient), 5_0000000);
+}
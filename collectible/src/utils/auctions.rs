@@ -0,0 +1,102 @@
+use crate::bumps::{AUCTIONS_BUMP_CONSTANT, AUCTIONS_BUMP_CONSTANT_THRESHOLD};
+use crate::errors::SCErrors;
+use crate::storage::auctions::{Auction, AuctionsDataKeys};
+use num_integer::div_floor;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// If a bid is placed when there are fewer ledgers than this value left, the auction is extended by the same amount
+pub(crate) const AUCTION_EXTENSION_LEDGERS: u32 = 120;
+
+pub fn bump_auction(env: &Env, item_number: &u64) {
+    if env
+        .storage()
+        .persistent()
+        .has(&AuctionsDataKeys::Auction(*item_number))
+    {
+        env.storage().persistent().extend_ttl(
+            &AuctionsDataKeys::Auction(*item_number),
+            AUCTIONS_BUMP_CONSTANT_THRESHOLD,
+            AUCTIONS_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn is_in_auction(env: &Env, item_number: &u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&AuctionsDataKeys::Auction(*item_number))
+}
+
+pub fn get_auction(env: &Env, item_number: &u64) -> Auction {
+    if is_in_auction(env, item_number) {
+        env.storage()
+            .persistent()
+            .get(&AuctionsDataKeys::Auction(*item_number))
+            .unwrap()
+    } else {
+        panic_with_error!(env, &SCErrors::AuctionDoesNotExist);
+    }
+}
+
+pub fn write_auction(env: &Env, auction: &Auction) {
+    env.storage()
+        .persistent()
+        .set(&AuctionsDataKeys::Auction(auction.item_number), auction);
+}
+
+pub fn remove_auction(env: &Env, item_number: &u64) {
+    env.storage()
+        .persistent()
+        .remove(&AuctionsDataKeys::Auction(*item_number));
+}
+
+pub fn bump_bid_refund(env: &Env, bidder: &Address) {
+    if env
+        .storage()
+        .persistent()
+        .has(&AuctionsDataKeys::BidRefund(bidder.clone()))
+    {
+        env.storage().persistent().extend_ttl(
+            &AuctionsDataKeys::BidRefund(bidder.clone()),
+            AUCTIONS_BUMP_CONSTANT_THRESHOLD,
+            AUCTIONS_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn get_bid_refund(env: &Env, bidder: &Address) -> u128 {
+    env.storage()
+        .persistent()
+        .get(&AuctionsDataKeys::BidRefund(bidder.clone()))
+        .unwrap_or(0)
+}
+
+pub fn write_bid_refund(env: &Env, bidder: &Address, amount: &u128) {
+    env.storage()
+        .persistent()
+        .set(&AuctionsDataKeys::BidRefund(bidder.clone()), amount);
+}
+
+pub fn remove_bid_refund(env: &Env, bidder: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&AuctionsDataKeys::BidRefund(bidder.clone()));
+}
+
+pub fn has_bids(auction: &Auction) -> bool {
+    auction.highest_bid > 0
+}
+
+/// The first bid only needs to reach the reserve, after that each bid needs to be higher than the current one by at
+/// least the minimum increment
+pub fn min_next_bid(auction: &Auction) -> u128 {
+    if !has_bids(auction) {
+        auction.reserve
+    } else {
+        let increment: u128 = div_floor(
+            auction.highest_bid * (auction.min_increment_bps as u128),
+            10000,
+        );
+        auction.highest_bid + increment.max(1)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::bumps::{BUNDLES_BUMP_CONSTANT, BUNDLES_BUMP_CONSTANT_THRESHOLD};
+use crate::errors::SCErrors;
+use crate::storage::bundles::{Bundle, BundlesDataKeys};
+use soroban_sdk::{panic_with_error, Env};
+
+pub fn bump_bundle(env: &Env, id: &u64) {
+    if env
+        .storage()
+        .persistent()
+        .has(&BundlesDataKeys::Bundle(*id))
+    {
+        env.storage().persistent().extend_ttl(
+            &BundlesDataKeys::Bundle(*id),
+            BUNDLES_BUMP_CONSTANT_THRESHOLD,
+            BUNDLES_BUMP_CONSTANT,
+        );
+
+        for item_number in get_bundle(env, id).items.iter() {
+            env.storage().persistent().extend_ttl(
+                &BundlesDataKeys::ItemBundle(item_number),
+                BUNDLES_BUMP_CONSTANT_THRESHOLD,
+                BUNDLES_BUMP_CONSTANT,
+            );
+        }
+    }
+}
+
+pub fn get_bundle(env: &Env, id: &u64) -> Bundle {
+    if env
+        .storage()
+        .persistent()
+        .has(&BundlesDataKeys::Bundle(*id))
+    {
+        env.storage()
+            .persistent()
+            .get(&BundlesDataKeys::Bundle(*id))
+            .unwrap()
+    } else {
+        panic_with_error!(env, &SCErrors::BundleDoesNotExist);
+    }
+}
+
+pub fn is_in_bundle(env: &Env, item_number: &u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&BundlesDataKeys::ItemBundle(*item_number))
+}
+
+/// Saves the Bundle and links each one of its Items to it
+pub fn write_bundle(env: &Env, bundle: &Bundle) {
+    env.storage()
+        .persistent()
+        .set(&BundlesDataKeys::Bundle(bundle.id), bundle);
+
+    for item_number in bundle.items.iter() {
+        env.storage()
+            .persistent()
+            .set(&BundlesDataKeys::ItemBundle(item_number), &bundle.id);
+    }
+}
+
+/// Removes the Bundle and releases its Items
+pub fn remove_bundle(env: &Env, bundle: &Bundle) {
+    env.storage()
+        .persistent()
+        .remove(&BundlesDataKeys::Bundle(bundle.id));
+
+    for item_number in bundle.items.iter() {
+        env.storage()
+            .persistent()
+            .remove(&BundlesDataKeys::ItemBundle(item_number));
+    }
+}
+
+pub fn next_bundle_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&BundlesDataKeys::LastBundleId)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&BundlesDataKeys::LastBundleId, &id);
+    id
+}
@@ -1,5 +1,6 @@
-use crate::storage::core::{CoreData, CoreDataKeys, TokenMetadata};
-use soroban_sdk::{token, Env};
+use crate::errors::SCErrors;
+use crate::storage::core::{CoreData, CoreDataKeys, PrimarySale, TokenMetadata};
+use soroban_sdk::{panic_with_error, token, Bytes, BytesN, Env, String};
 
 use crate::bumps::{INSTANCE_BUMP_CONSTANT, INSTANCE_BUMP_CONSTANT_THRESHOLD};
 
@@ -26,6 +27,23 @@ pub fn get_core_data(env: &Env) -> CoreData {
         .unwrap()
 }
 
+pub fn write_primary_sale(env: &Env, primary_sale: &PrimarySale) {
+    env.storage()
+        .instance()
+        .set(&CoreDataKeys::PrimarySale, primary_sale);
+}
+
+pub fn get_primary_sale(env: &Env) -> PrimarySale {
+    env.storage()
+        .instance()
+        .get(&CoreDataKeys::PrimarySale)
+        .unwrap_or(PrimarySale {
+            start_ledger: 0,
+            end_ledger: u32::MAX,
+            paused: false,
+        })
+}
+
 pub fn get_metadata(env: &Env) -> TokenMetadata {
     env.storage()
         .instance()
@@ -39,6 +57,49 @@ pub fn write_token_metadata(env: &Env, token_metadata: TokenMetadata) {
         .set(&CoreDataKeys::TokenMetadata, &token_metadata);
 }
 
+/// The longest metadata URI that can be revealed
+pub const MAX_METADATA_URI_LENGTH: usize = 1024;
+
+pub fn write_metadata_commitment(env: &Env, hash: &BytesN<32>) {
+    env.storage()
+        .instance()
+        .set(&CoreDataKeys::MetadataCommitment, hash);
+}
+
+pub fn get_metadata_commitment(env: &Env) -> Option<BytesN<32>> {
+    env.storage()
+        .instance()
+        .get(&CoreDataKeys::MetadataCommitment)
+}
+
+pub fn write_metadata_revealed(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&CoreDataKeys::MetadataRevealed, &true);
+}
+
+pub fn is_metadata_revealed(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&CoreDataKeys::MetadataRevealed)
+        .unwrap_or(false)
+}
+
+/// The hash committed before the reveal, it's the sha256 hash of the URI bytes followed by the salt
+pub fn metadata_commitment_hash(env: &Env, uri: &String, salt: &Bytes) -> BytesN<32> {
+    let length: usize = uri.len() as usize;
+    if length > MAX_METADATA_URI_LENGTH {
+        panic_with_error!(env, &SCErrors::InvalidMetadataReveal);
+    }
+
+    let mut buffer: [u8; MAX_METADATA_URI_LENGTH] = [0; MAX_METADATA_URI_LENGTH];
+    uri.copy_into_slice(&mut buffer[..length]);
+
+    let mut preimage: Bytes = Bytes::from_slice(env, &buffer[..length]);
+    preimage.append(salt);
+    env.crypto().sha256(&preimage)
+}
+
 pub fn collection_currency<'a>(env: &Env, core_data: &CoreData) -> token::Client<'a> {
     token::Client::new(&env, &core_data.collection_currency)
 }
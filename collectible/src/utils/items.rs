@@ -1,8 +1,13 @@
 use crate::bumps::{ITEMS_BUMP_CONSTANT, ITEMS_BUMP_CONSTANT_THRESHOLD};
 use crate::errors::SCErrors;
 use crate::storage::core::CoreData;
-use crate::storage::items::{Item, ItemsDataKeys};
-use soroban_sdk::{panic_with_error, Env};
+use crate::storage::items::{Item, ItemsDataKeys, Listing, Reservation};
+use crate::utils::auctions::is_in_auction;
+use crate::utils::balances::{bump_balance, get_balance, write_balance};
+use crate::utils::bundles::is_in_bundle;
+use crate::utils::core::get_core_data;
+use crate::utils::sales::{is_primary_sale_open, is_reserved_item};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
 pub fn bump_item(env: &Env, number: &u64) {
     if env
@@ -16,6 +21,18 @@ pub fn bump_item(env: &Env, number: &u64) {
             ITEMS_BUMP_CONSTANT,
         );
     }
+
+    if env
+        .storage()
+        .persistent()
+        .has(&ItemsDataKeys::Listing(*number))
+    {
+        env.storage().persistent().extend_ttl(
+            &ItemsDataKeys::Listing(*number),
+            ITEMS_BUMP_CONSTANT_THRESHOLD,
+            ITEMS_BUMP_CONSTANT,
+        );
+    }
 }
 
 pub fn is_minted(env: &Env, number: &u64) -> bool {
@@ -45,13 +62,275 @@ pub fn write_item(env: &Env, item: &Item) {
         .set(&ItemsDataKeys::Item(item.number.clone()), item);
 }
 
+/// Returns the conditions of the Item sale offer, if they were never set the offer has no conditions
+pub fn get_listing(env: &Env, number: &u64) -> Listing {
+    env.storage()
+        .persistent()
+        .get(&ItemsDataKeys::Listing(*number))
+        .unwrap_or(Listing {
+            expiration_ledger: None,
+            reserved_for: Reservation::None,
+        })
+}
+
+pub fn write_listing(env: &Env, number: &u64, listing: &Listing) {
+    env.storage()
+        .persistent()
+        .set(&ItemsDataKeys::Listing(*number), listing);
+}
+
+pub fn is_listing_expired(env: &Env, number: &u64) -> bool {
+    match get_listing(env, number).expiration_ledger {
+        Some(expiration_ledger) => expiration_ledger < env.ledger().sequence(),
+        None => false,
+    }
+}
+
 /// An Item is for sale in two situations:
-/// - The owner puts it for sale on an offer IE Item.for_sale == true
-/// - The Item record doesn't exist, this means it's still on the first sale IE hasn't been "minted" yet
+/// - The owner puts it for sale on an offer IE Item.for_sale == true and the offer hasn't expired
+/// - The Item record doesn't exist, this means it's still on the first sale IE hasn't been "minted" yet, the Item is not
+///   reserved and the primary sale is open
 pub fn is_item_for_sale(env: &Env, number: &u64) -> bool {
-    if is_minted(&env, &number) {
-        get_item(&env, &number).for_sale
+    if is_minted(env, number) {
+        let item: Item = get_item(env, number);
+        item.for_sale && !is_listing_expired(env, number)
     } else {
-        true
+        !is_reserved_item(env, number) && is_primary_sale_open(env)
+    }
+}
+
+pub fn has_minted_count(env: &Env) -> bool {
+    env.storage().instance().has(&ItemsDataKeys::MintedCount)
+}
+
+pub fn write_minted_count(env: &Env, count: &u64) {
+    env.storage()
+        .instance()
+        .set(&ItemsDataKeys::MintedCount, count);
+}
+
+pub fn get_minted_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&ItemsDataKeys::MintedCount)
+        .unwrap_or(0)
+}
+
+pub fn get_unminted_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&ItemsDataKeys::UnmintedCount)
+        .unwrap_or(get_core_data(env).supply + 1)
+}
+
+/// Slots and positions are only stored once they are moved, they get bumped every time they are read or written
+fn bump_unminted_entry(env: &Env, key: &ItemsDataKeys) {
+    if env.storage().persistent().has(key) {
+        env.storage().persistent().extend_ttl(
+            key,
+            ITEMS_BUMP_CONSTANT_THRESHOLD,
+            ITEMS_BUMP_CONSTANT,
+        );
     }
 }
+
+fn get_unminted_slot(env: &Env, index: &u64) -> u64 {
+    let key: ItemsDataKeys = ItemsDataKeys::UnmintedSlot(*index);
+    bump_unminted_entry(env, &key);
+    env.storage().persistent().get(&key).unwrap_or(*index)
+}
+
+fn get_unminted_position(env: &Env, number: &u64) -> u64 {
+    let key: ItemsDataKeys = ItemsDataKeys::UnmintedPosition(*number);
+    bump_unminted_entry(env, &key);
+    env.storage().persistent().get(&key).unwrap_or(*number)
+}
+
+fn write_unminted_slot(env: &Env, index: &u64, number: &u64) {
+    env.storage()
+        .persistent()
+        .set(&ItemsDataKeys::UnmintedSlot(*index), number);
+    env.storage()
+        .persistent()
+        .set(&ItemsDataKeys::UnmintedPosition(*number), index);
+
+    bump_unminted_entry(env, &ItemsDataKeys::UnmintedSlot(*index));
+    bump_unminted_entry(env, &ItemsDataKeys::UnmintedPosition(*number));
+}
+
+/// Returns the Item at the given index of the unminted list, the index must be lower than the unminted count
+pub fn get_unminted_item(env: &Env, index: &u64) -> u64 {
+    get_unminted_slot(env, index)
+}
+
+/// Slots that were never moved hold their own Item by default, so an Item is only in the list if the slot at its
+/// position points back to it
+pub fn is_unminted_item(env: &Env, number: &u64) -> bool {
+    let position: u64 = get_unminted_position(env, number);
+    position < get_unminted_count(env) && get_unminted_slot(env, &position) == *number
+}
+
+/// Adds the Item at the end of the unminted list, it does nothing if the Item is already there
+pub fn add_unminted_item(env: &Env, number: &u64) {
+    if is_unminted_item(env, number) {
+        return;
+    }
+
+    let count: u64 = get_unminted_count(env);
+    write_unminted_slot(env, &count, number);
+    env.storage()
+        .instance()
+        .set(&ItemsDataKeys::UnmintedCount, &(count + 1));
+}
+
+/// Removes the Item from the unminted list by moving the last Item of the list to its position
+pub fn remove_unminted_item(env: &Env, number: &u64) {
+    if !is_unminted_item(env, number) {
+        return;
+    }
+
+    let position: u64 = get_unminted_position(env, number);
+    let last_index: u64 = get_unminted_count(env) - 1;
+    if position != last_index {
+        let last_number: u64 = get_unminted_slot(env, &last_index);
+        write_unminted_slot(env, &position, &last_number);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&ItemsDataKeys::UnmintedSlot(last_index));
+    env.storage()
+        .persistent()
+        .remove(&ItemsDataKeys::UnmintedPosition(*number));
+    env.storage()
+        .instance()
+        .set(&ItemsDataKeys::UnmintedCount, &last_index);
+}
+
+/// The maximum number of draws "pick_random_unminted_item" does before giving up, so a list full of Items that can't be
+/// sold can't exhaust the budget of the transaction
+pub(crate) const MAX_RANDOM_DRAWS: u32 = 20;
+
+/// Picks a random Item from the unminted list, reserved Items stay in the list so a new draw is done when one of them
+/// is picked. Items minted before the unminted list existed are still in it, those are removed once they are found and
+/// a new draw is done. If no Item can be sold after "MAX_RANDOM_DRAWS" draws it fails with "ItemIsNotForSale"
+pub fn pick_random_unminted_item(env: &Env) -> u64 {
+    for _ in 0..MAX_RANDOM_DRAWS {
+        let count: u64 = get_unminted_count(env);
+        if count == 0 {
+            panic_with_error!(env, &SCErrors::AllItemsAreMinted);
+        }
+
+        let number: u64 = get_unminted_item(env, &env.prng().gen_range(0..count));
+        if is_minted(env, &number) {
+            remove_unminted_item(env, &number);
+        } else if !is_reserved_item(env, &number) {
+            return number;
+        }
+    }
+
+    panic_with_error!(env, &SCErrors::ItemIsNotForSale);
+}
+
+/// Creates the Item record for an Item that hasn't been minted yet and increases the balance of the new owner
+pub fn mint_item(env: &Env, number: &u64, to: &Address) {
+    remove_unminted_item(env, number);
+    write_minted_count(env, &(get_minted_count(env) + 1));
+
+    let new_owner_balance: u128 = get_balance(env, to);
+    write_balance(env, to, &(new_owner_balance + 1));
+    bump_balance(env, to);
+
+    write_item(
+        env,
+        &Item {
+            number: *number,
+            for_sale: false,
+            owner: to.clone(),
+            price: 0,
+        },
+    );
+}
+
+/// Removes the Item record and puts the Item back in the unminted list, the opposite of "mint_item"
+pub fn unmint_item(env: &Env, item: &Item) {
+    add_unminted_item(env, &item.number);
+    write_minted_count(env, &(get_minted_count(env) - 1));
+
+    let owner_balance: u128 = get_balance(env, &item.owner);
+    write_balance(env, &item.owner, &(owner_balance - 1));
+    bump_balance(env, &item.owner);
+
+    env.storage()
+        .persistent()
+        .remove(&ItemsDataKeys::Item(item.number));
+    env.storage()
+        .persistent()
+        .remove(&ItemsDataKeys::Listing(item.number));
+}
+
+/// Items that are being auctioned or are part of an active bundle can't be sold, transferred or used in any other
+/// operation until the auction is settled or the bundle is sold or cancelled
+pub fn check_item_is_not_locked(env: &Env, number: &u64) {
+    if is_in_auction(env, number) {
+        panic_with_error!(env, &SCErrors::ItemIsInAuction);
+    }
+
+    if is_in_bundle(env, number) {
+        panic_with_error!(env, &SCErrors::ItemIsInBundle);
+    }
+}
+
+/// Moves an already minted Item to a new owner, the balances of both accounts are updated and if the Item was for sale
+/// the offer gets removed
+pub fn change_owner(env: &Env, item: &mut Item, to: &Address) {
+    let current_owner_balance: u128 = get_balance(env, &item.owner);
+    write_balance(env, &item.owner, &(current_owner_balance - 1));
+    bump_balance(env, &item.owner);
+
+    let new_owner_balance: u128 = get_balance(env, to);
+    write_balance(env, to, &(new_owner_balance + 1));
+    bump_balance(env, to);
+
+    item.owner = to.clone();
+    remove_listing(env, item);
+    write_item(env, item);
+}
+
+/// Resets the sale offer values of an Item and removes its Listing, it doesn't write the Item into the storage
+pub fn remove_listing(env: &Env, item: &mut Item) {
+    item.for_sale = false;
+    item.price = 0;
+    env.storage()
+        .persistent()
+        .remove(&ItemsDataKeys::Listing(item.number));
+}
+
+/// Returns false if the sale offer is reserved for someone else
+pub fn can_buy_listing(env: &Env, number: &u64, buyer: &Address) -> bool {
+    match &get_listing(env, number).reserved_for {
+        Reservation::None => true,
+        Reservation::Buyer(reserved_buyer) => reserved_buyer == buyer,
+    }
+}
+
+/// Returns the Items of the list making sure all of them are owned by the expected owner, none of them is locked and
+/// there are no duplicated Items
+pub fn get_owned_items(env: &Env, item_numbers: &Vec<u64>, owner: &Address) -> Vec<Item> {
+    let mut items: Vec<Item> = Vec::new(env);
+    for item_number in item_numbers.iter() {
+        if items.iter().any(|item| item.number == item_number) {
+            panic_with_error!(env, &SCErrors::ItemIsDuplicated);
+        }
+
+        let item: Item = get_item(env, &item_number);
+        if &item.owner != owner {
+            panic_with_error!(env, &SCErrors::ItemIsNotOwned);
+        }
+
+        check_item_is_not_locked(env, &item_number);
+        items.push_back(item);
+    }
+
+    items
+}
@@ -0,0 +1,86 @@
+use crate::bumps::{OFFERS_BUMP_CONSTANT, OFFERS_BUMP_CONSTANT_THRESHOLD};
+use crate::errors::SCErrors;
+use crate::storage::offers::{CollectionBid, Offer, OffersDataKeys};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+pub fn bump_offer(env: &Env, item_number: &u64, bidder: &Address) {
+    let key = OffersDataKeys::Offer(*item_number, bidder.clone());
+    if env.storage().persistent().has(&key) {
+        env.storage().persistent().extend_ttl(
+            &key,
+            OFFERS_BUMP_CONSTANT_THRESHOLD,
+            OFFERS_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn has_offer(env: &Env, item_number: &u64, bidder: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&OffersDataKeys::Offer(*item_number, bidder.clone()))
+}
+
+pub fn get_offer(env: &Env, item_number: &u64, bidder: &Address) -> Offer {
+    if has_offer(env, item_number, bidder) {
+        env.storage()
+            .persistent()
+            .get(&OffersDataKeys::Offer(*item_number, bidder.clone()))
+            .unwrap()
+    } else {
+        panic_with_error!(env, &SCErrors::OfferDoesNotExist);
+    }
+}
+
+pub fn write_offer(env: &Env, offer: &Offer) {
+    env.storage().persistent().set(
+        &OffersDataKeys::Offer(offer.item_number, offer.bidder.clone()),
+        offer,
+    );
+}
+
+pub fn remove_offer(env: &Env, item_number: &u64, bidder: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&OffersDataKeys::Offer(*item_number, bidder.clone()));
+}
+
+pub fn bump_collection_bid(env: &Env, bidder: &Address) {
+    let key = OffersDataKeys::CollectionBid(bidder.clone());
+    if env.storage().persistent().has(&key) {
+        env.storage().persistent().extend_ttl(
+            &key,
+            OFFERS_BUMP_CONSTANT_THRESHOLD,
+            OFFERS_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn has_collection_bid(env: &Env, bidder: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&OffersDataKeys::CollectionBid(bidder.clone()))
+}
+
+pub fn get_collection_bid(env: &Env, bidder: &Address) -> CollectionBid {
+    if has_collection_bid(env, bidder) {
+        env.storage()
+            .persistent()
+            .get(&OffersDataKeys::CollectionBid(bidder.clone()))
+            .unwrap()
+    } else {
+        panic_with_error!(env, &SCErrors::CollectionBidDoesNotExist);
+    }
+}
+
+pub fn write_collection_bid(env: &Env, collection_bid: &CollectionBid) {
+    env.storage().persistent().set(
+        &OffersDataKeys::CollectionBid(collection_bid.bidder.clone()),
+        collection_bid,
+    );
+}
+
+pub fn remove_collection_bid(env: &Env, bidder: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&OffersDataKeys::CollectionBid(bidder.clone()));
+}
@@ -1,6 +1,21 @@
-use crate::bumps::{ROYALTIES_BUMP_CONSTANT, ROYALTIES_BUMP_CONSTANT_THRESHOLD};
-use crate::storage::royalties::{RoyaltiesDataKeys, Royalty};
-use soroban_sdk::{Env, Vec};
+use crate::bumps::{DAY_IN_LEDGERS, ROYALTIES_BUMP_CONSTANT, ROYALTIES_BUMP_CONSTANT_THRESHOLD};
+use crate::errors::SCRoyaltyErrors;
+use crate::events;
+use crate::storage::royalties::{
+    LegacyRoyalty, PendingItemRoyalties, PendingRoyalties, RoyaltiesDataKeys, Royalty,
+};
+use crate::utils::sales::add_payout;
+use num_integer::div_floor;
+use soroban_sdk::{panic_with_error, Address, Env, Map, Vec};
+
+/// The maximum number of royalties a collectible can have, each one is paid on every sale
+pub const MAX_ROYALTIES: u32 = 10;
+
+/// The current version of the royalties format, version "1" is the legacy format
+pub const ROYALTIES_VERSION: u32 = 2;
+
+/// The delay used to apply new royalties if the admin hasn't defined a longer one
+pub const DEFAULT_ROYALTIES_DELAY: u32 = DAY_IN_LEDGERS * 7;
 
 pub fn bump_royalties(env: &Env) {
     env.storage().persistent().extend_ttl(
@@ -10,15 +25,320 @@ pub fn bump_royalties(env: &Env) {
     );
 }
 
+/// Panics if the royalties can't be paid on every sale, the total of the primary percentages and the total of the
+/// secondary percentages can't be higher than 1_0000000 (100%)
+pub fn validate_royalties(env: &Env, royalties: &Vec<Royalty>) {
+    if royalties.len() > MAX_ROYALTIES {
+        panic_with_error!(env, &SCRoyaltyErrors::TooManyRoyalties);
+    }
+
+    let mut primary_total: u128 = 0;
+    let mut secondary_total: u128 = 0;
+    let mut addresses: Map<Address, bool> = Map::new(env);
+    for royalty in royalties.iter() {
+        if royalty.name.len() == 0 {
+            panic_with_error!(env, &SCRoyaltyErrors::RoyaltyNameIsEmpty);
+        }
+
+        if royalty.primary_percentage == 0 && royalty.secondary_percentage == 0 {
+            panic_with_error!(env, &SCRoyaltyErrors::RoyaltyPercentageIsZero);
+        }
+
+        if addresses.contains_key(royalty.address.clone()) {
+            panic_with_error!(env, &SCRoyaltyErrors::RoyaltyAddressIsDuplicated);
+        }
+        addresses.set(royalty.address.clone(), true);
+
+        primary_total = primary_total.saturating_add(royalty.primary_percentage);
+        secondary_total = secondary_total.saturating_add(royalty.secondary_percentage);
+    }
+
+    if primary_total > 1_0000000 || secondary_total > 1_0000000 {
+        panic_with_error!(env, &SCRoyaltyErrors::RoyaltiesAreHigherThanPrice);
+    }
+}
+
+pub fn write_royalties_version(env: &Env, version: &u32) {
+    env.storage()
+        .instance()
+        .set(&RoyaltiesDataKeys::RoyaltiesVersion, version);
+}
+
+pub fn get_royalties_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&RoyaltiesDataKeys::RoyaltiesVersion)
+        .unwrap_or(1)
+}
+
+/// A legacy royalty keeps its percentage on the first sale, and also on the rest of the sales unless it was a
+/// "first_sale" royalty
+pub fn from_legacy_royalties(env: &Env, legacy_royalties: &Vec<LegacyRoyalty>) -> Vec<Royalty> {
+    let mut royalties: Vec<Royalty> = Vec::new(env);
+    for legacy_royalty in legacy_royalties.iter() {
+        royalties.push_back(Royalty {
+            address: legacy_royalty.address,
+            name: legacy_royalty.name,
+            primary_percentage: legacy_royalty.percentage,
+            secondary_percentage: if legacy_royalty.first_sale {
+                0
+            } else {
+                legacy_royalty.percentage
+            },
+        });
+    }
+
+    royalties
+}
+
+/// Panics if the royalties are still saved in the legacy format, they can't be read until they are migrated
+pub fn check_royalties_are_migrated(env: &Env) {
+    if get_royalties_version(env) < ROYALTIES_VERSION {
+        panic_with_error!(env, &SCRoyaltyErrors::RoyaltiesAreNotMigrated);
+    }
+}
+
+/// Rewrites the royalties of the collection using the current format, it can only be done once
+/// The first version of the contract only saved the royalties of the collection, so those are the only ones converted
+/// The converted royalties are saved as they are, legacy values didn't go through the current validation and
+/// rejecting them would leave them unreadable. The admin can replace them with a new proposal afterwards
+pub fn migrate_royalties(env: &Env) {
+    if get_royalties_version(env) >= ROYALTIES_VERSION {
+        panic_with_error!(env, &SCRoyaltyErrors::RoyaltiesAreAlreadyMigrated);
+    }
+
+    let legacy_royalties: Vec<LegacyRoyalty> = env
+        .storage()
+        .persistent()
+        .get(&RoyaltiesDataKeys::Royalties)
+        .unwrap();
+    env.storage().persistent().set(
+        &RoyaltiesDataKeys::Royalties,
+        &from_legacy_royalties(env, &legacy_royalties),
+    );
+
+    write_royalties_version(env, &ROYALTIES_VERSION);
+}
+
+/// The royalties are always validated before they are saved
 pub fn write_royalties(env: &Env, royalties: &Vec<Royalty>) {
+    check_royalties_are_migrated(env);
+    validate_royalties(env, royalties);
     env.storage()
         .persistent()
         .set(&RoyaltiesDataKeys::Royalties, royalties)
 }
 
 pub fn get_royalties(env: &Env) -> Vec<Royalty> {
+    check_royalties_are_migrated(env);
     env.storage()
         .persistent()
         .get(&RoyaltiesDataKeys::Royalties)
         .unwrap()
 }
+
+pub fn bump_item_royalties(env: &Env, item_number: &u64) {
+    if env
+        .storage()
+        .persistent()
+        .has(&RoyaltiesDataKeys::ItemRoyalties(*item_number))
+    {
+        env.storage().persistent().extend_ttl(
+            &RoyaltiesDataKeys::ItemRoyalties(*item_number),
+            ROYALTIES_BUMP_CONSTANT_THRESHOLD,
+            ROYALTIES_BUMP_CONSTANT,
+        );
+    }
+}
+
+/// The royalties are validated before they are saved, sending None removes the override
+pub fn write_item_royalties(env: &Env, item_number: &u64, royalties: &Option<Vec<Royalty>>) {
+    match royalties {
+        Some(royalties) => {
+            validate_royalties(env, royalties);
+            env.storage()
+                .persistent()
+                .set(&RoyaltiesDataKeys::ItemRoyalties(*item_number), royalties);
+        }
+        None => env
+            .storage()
+            .persistent()
+            .remove(&RoyaltiesDataKeys::ItemRoyalties(*item_number)),
+    }
+}
+
+pub fn get_item_royalties_override(env: &Env, item_number: &u64) -> Option<Vec<Royalty>> {
+    env.storage()
+        .persistent()
+        .get(&RoyaltiesDataKeys::ItemRoyalties(*item_number))
+}
+
+/// The royalties paid when the Item is sold, the override of the Item if it has one or the collection royalties
+pub fn get_item_royalties(env: &Env, item_number: &u64) -> Vec<Royalty> {
+    match get_item_royalties_override(env, item_number) {
+        Some(royalties) => {
+            bump_item_royalties(env, item_number);
+            royalties
+        }
+        None => get_royalties(env),
+    }
+}
+
+pub fn bump_pending_item_royalties(env: &Env, item_number: &u64) {
+    if env
+        .storage()
+        .persistent()
+        .has(&RoyaltiesDataKeys::PendingItemRoyalties(*item_number))
+    {
+        env.storage().persistent().extend_ttl(
+            &RoyaltiesDataKeys::PendingItemRoyalties(*item_number),
+            ROYALTIES_BUMP_CONSTANT_THRESHOLD,
+            ROYALTIES_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn write_pending_item_royalties(
+    env: &Env,
+    item_number: &u64,
+    pending_item_royalties: &PendingItemRoyalties,
+) {
+    env.storage().persistent().set(
+        &RoyaltiesDataKeys::PendingItemRoyalties(*item_number),
+        pending_item_royalties,
+    );
+}
+
+pub fn remove_pending_item_royalties(env: &Env, item_number: &u64) {
+    env.storage()
+        .persistent()
+        .remove(&RoyaltiesDataKeys::PendingItemRoyalties(*item_number));
+}
+
+pub fn get_pending_item_royalties(env: &Env, item_number: &u64) -> PendingItemRoyalties {
+    match env
+        .storage()
+        .persistent()
+        .get(&RoyaltiesDataKeys::PendingItemRoyalties(*item_number))
+    {
+        Some(pending_item_royalties) => pending_item_royalties,
+        None => panic_with_error!(env, &SCRoyaltyErrors::PendingRoyaltiesDoNotExist),
+    }
+}
+
+pub fn bump_pending_royalties(env: &Env) {
+    if env
+        .storage()
+        .persistent()
+        .has(&RoyaltiesDataKeys::PendingRoyalties)
+    {
+        env.storage().persistent().extend_ttl(
+            &RoyaltiesDataKeys::PendingRoyalties,
+            ROYALTIES_BUMP_CONSTANT_THRESHOLD,
+            ROYALTIES_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn write_pending_royalties(env: &Env, pending_royalties: &PendingRoyalties) {
+    env.storage()
+        .persistent()
+        .set(&RoyaltiesDataKeys::PendingRoyalties, pending_royalties);
+}
+
+pub fn remove_pending_royalties(env: &Env) {
+    env.storage()
+        .persistent()
+        .remove(&RoyaltiesDataKeys::PendingRoyalties);
+}
+
+pub fn get_pending_royalties(env: &Env) -> PendingRoyalties {
+    match env
+        .storage()
+        .persistent()
+        .get(&RoyaltiesDataKeys::PendingRoyalties)
+    {
+        Some(pending_royalties) => pending_royalties,
+        None => panic_with_error!(env, &SCRoyaltyErrors::PendingRoyaltiesDoNotExist),
+    }
+}
+
+pub fn write_royalties_delay(env: &Env, ledgers: &u32) {
+    env.storage()
+        .instance()
+        .set(&RoyaltiesDataKeys::RoyaltiesDelay, ledgers);
+}
+
+pub fn get_royalties_delay(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&RoyaltiesDataKeys::RoyaltiesDelay)
+        .unwrap_or(DEFAULT_ROYALTIES_DELAY)
+}
+
+pub fn write_royalties_frozen(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&RoyaltiesDataKeys::RoyaltiesFrozen, &true);
+}
+
+pub fn is_royalties_frozen(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&RoyaltiesDataKeys::RoyaltiesFrozen)
+        .unwrap_or(false)
+}
+
+pub fn check_royalties_are_not_frozen(env: &Env) {
+    if is_royalties_frozen(env) {
+        panic_with_error!(env, &SCRoyaltyErrors::RoyaltiesAreFrozen);
+    }
+}
+
+/// The amount each royalty receives from a sale, it uses the primary percentage on the first sale of the item and the
+/// secondary percentage on the rest. Royalties with a percentage of "0" for the sale are ignored
+pub fn royalty_shares(
+    env: &Env,
+    royalties: &Vec<Royalty>,
+    price: &u128,
+    is_first_sale: bool,
+) -> Vec<(Address, u128)> {
+    let mut shares: Vec<(Address, u128)> = Vec::new(env);
+    for royalty in royalties.iter() {
+        let percentage: u128 = if is_first_sale {
+            royalty.primary_percentage
+        } else {
+            royalty.secondary_percentage
+        };
+
+        if percentage == 0 {
+            continue;
+        }
+
+        let share = div_floor(price * percentage, 1_0000000);
+        shares.push_back((royalty.address, share));
+    }
+
+    shares
+}
+
+/// Adds the royalties of a sale to the payouts
+/// It returns the total amount of royalties so the caller can pay the rest to the seller
+pub fn collect_royalties(
+    env: &Env,
+    royalties: &Vec<Royalty>,
+    payouts: &mut Map<Address, u128>,
+    item_number: &u64,
+    price: &u128,
+    is_first_sale: bool,
+) -> u128 {
+    let mut royalties_distributed: u128 = 0u128;
+    for (address, share) in royalty_shares(env, royalties, price, is_first_sale).iter() {
+        add_payout(payouts, &address, share);
+        royalties_distributed += share;
+
+        events::royalty_payment(env, *item_number, address, share);
+    }
+
+    royalties_distributed
+}
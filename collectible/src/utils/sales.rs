@@ -0,0 +1,635 @@
+use crate::bumps::{
+    ESCROWED_SALES_BUMP_CONSTANT, ESCROWED_SALES_BUMP_CONSTANT_THRESHOLD,
+    PRICE_OVERRIDES_BUMP_CONSTANT, PRICE_OVERRIDES_BUMP_CONSTANT_THRESHOLD,
+    PRIMARY_PURCHASES_BUMP_CONSTANT, PRIMARY_PURCHASES_BUMP_CONSTANT_THRESHOLD,
+};
+use crate::errors::SCErrors;
+use crate::events;
+use crate::storage::core::{CoreData, PrimarySale};
+use crate::storage::items::Item;
+use crate::storage::royalties::Royalty;
+use crate::storage::sales::{
+    Allowlist, BondingCurve, BondingCurveKind, DutchAuction, EscrowedSale, PriceTier,
+    ReservedItems, SalePhase, SalesDataKeys,
+};
+use crate::utils::core::{collection_currency, get_core_data, get_primary_sale};
+use crate::utils::items::{
+    bump_item, can_buy_listing, change_owner, get_item, get_minted_count, is_item_for_sale,
+    is_listing_expired, is_minted, is_valid_item_number, mint_item,
+};
+use crate::utils::royalties::{
+    collect_royalties, get_item_royalties, get_item_royalties_override, get_royalties,
+};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, token, Address, Bytes, BytesN, Env, Map, Vec};
+
+pub fn write_dutch_auction(env: &Env, dutch_auction: &DutchAuction) {
+    env.storage()
+        .instance()
+        .set(&SalesDataKeys::DutchAuction, dutch_auction);
+}
+
+pub fn remove_dutch_auction(env: &Env) {
+    env.storage()
+        .instance()
+        .remove(&SalesDataKeys::DutchAuction);
+}
+
+pub fn get_dutch_auction(env: &Env) -> Option<DutchAuction> {
+    env.storage().instance().get(&SalesDataKeys::DutchAuction)
+}
+
+pub fn dutch_auction_price(env: &Env, dutch_auction: &DutchAuction) -> u128 {
+    let current_ledger: u32 = env.ledger().sequence();
+    if current_ledger <= dutch_auction.start_ledger {
+        return dutch_auction.start_price;
+    }
+
+    let elapsed: u128 = (current_ledger - dutch_auction.start_ledger) as u128;
+    let decay: u128 = elapsed.saturating_mul(dutch_auction.decay_per_ledger);
+
+    dutch_auction
+        .start_price
+        .saturating_sub(decay)
+        .max(dutch_auction.floor_price)
+}
+
+pub fn write_bonding_curve(env: &Env, bonding_curve: &BondingCurve) {
+    env.storage()
+        .instance()
+        .set(&SalesDataKeys::BondingCurve, bonding_curve);
+}
+
+pub fn remove_bonding_curve(env: &Env) {
+    env.storage()
+        .instance()
+        .remove(&SalesDataKeys::BondingCurve);
+}
+
+pub fn get_bonding_curve(env: &Env) -> Option<BondingCurve> {
+    env.storage().instance().get(&SalesDataKeys::BondingCurve)
+}
+
+/// The price of the next Item once "minted" Items have been minted, it fails with "BondingCurvePriceOverflow" if the
+/// price doesn't fit in a u128
+pub fn bonding_curve_price(env: &Env, bonding_curve: &BondingCurve, minted: u64) -> u128 {
+    let price: Option<u128> = match bonding_curve.kind {
+        BondingCurveKind::Linear => bonding_curve
+            .increment
+            .checked_mul(minted as u128)
+            .and_then(|increase| bonding_curve.base_price.checked_add(increase)),
+        BondingCurveKind::Exponential => {
+            // (1 + increment) ^ minted using 7 decimals and exponentiation by squaring
+            let scale: u128 = 1_0000000;
+            let mut factor: Option<u128> = bonding_curve.increment.checked_add(scale);
+            let mut result: Option<u128> = Some(scale);
+            let mut exponent: u64 = minted;
+            while exponent > 0 {
+                if exponent & 1 == 1 {
+                    result = result
+                        .zip(factor)
+                        .and_then(|(result, factor)| result.checked_mul(factor))
+                        .map(|result| result / scale);
+                }
+                exponent >>= 1;
+                if exponent > 0 {
+                    factor = factor
+                        .and_then(|factor| factor.checked_mul(factor))
+                        .map(|factor| factor / scale);
+                }
+            }
+
+            result
+                .and_then(|result| bonding_curve.base_price.checked_mul(result))
+                .map(|price| price / scale)
+        }
+    };
+
+    match price {
+        Some(price) => price,
+        None => panic_with_error!(env, &SCErrors::BondingCurvePriceOverflow),
+    }
+}
+
+pub fn write_sale_phases(env: &Env, sale_phases: &Vec<SalePhase>) {
+    env.storage()
+        .instance()
+        .set(&SalesDataKeys::SalePhases, sale_phases);
+}
+
+pub fn get_sale_phases(env: &Env) -> Vec<SalePhase> {
+    env.storage()
+        .instance()
+        .get(&SalesDataKeys::SalePhases)
+        .unwrap_or(Vec::new(env))
+}
+
+/// The active phase is the last one that has already started
+pub fn get_active_sale_phase(env: &Env) -> Option<SalePhase> {
+    let current_ledger: u32 = env.ledger().sequence();
+    let mut active_phase: Option<SalePhase> = None;
+    for phase in get_sale_phases(env).iter() {
+        if phase.start_ledger <= current_ledger {
+            active_phase = Some(phase);
+        }
+    }
+
+    active_phase
+}
+
+/// The primary sale is open if it's not paused, the current ledger is inside the sale window and, if there are sale
+/// phases, the first one has started
+pub fn is_primary_sale_open(env: &Env) -> bool {
+    let primary_sale: PrimarySale = get_primary_sale(env);
+    let current_ledger: u32 = env.ledger().sequence();
+
+    if primary_sale.paused
+        || current_ledger < primary_sale.start_ledger
+        || current_ledger > primary_sale.end_ledger
+    {
+        return false;
+    }
+
+    get_sale_phases(env).is_empty() || get_active_sale_phase(env).is_some()
+}
+
+/// Checks the proof going from the leaf to the root, each pair of nodes is sorted before hashing them
+pub fn verify_merkle_proof(
+    env: &Env,
+    root: &BytesN<32>,
+    leaf: &BytesN<32>,
+    proof: &Vec<BytesN<32>>,
+) -> bool {
+    let mut computed: BytesN<32> = leaf.clone();
+    for node in proof.iter() {
+        let mut data: Bytes = Bytes::new(env);
+        if computed < node {
+            data.append(&computed.into());
+            data.append(&node.into());
+        } else {
+            data.append(&node.into());
+            data.append(&computed.into());
+        }
+        computed = env.crypto().sha256(&data);
+    }
+
+    &computed == root
+}
+
+pub fn write_max_per_wallet(env: &Env, max_per_wallet: &Option<u32>) {
+    match max_per_wallet {
+        Some(max) => env
+            .storage()
+            .instance()
+            .set(&SalesDataKeys::MaxPerWallet, max),
+        None => env
+            .storage()
+            .instance()
+            .remove(&SalesDataKeys::MaxPerWallet),
+    }
+}
+
+pub fn get_max_per_wallet(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&SalesDataKeys::MaxPerWallet)
+}
+
+pub fn bump_primary_purchases(env: &Env, address: &Address) {
+    if env
+        .storage()
+        .persistent()
+        .has(&SalesDataKeys::PrimaryPurchases(address.clone()))
+    {
+        env.storage().persistent().extend_ttl(
+            &SalesDataKeys::PrimaryPurchases(address.clone()),
+            PRIMARY_PURCHASES_BUMP_CONSTANT_THRESHOLD,
+            PRIMARY_PURCHASES_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn write_primary_purchases(env: &Env, address: &Address, purchases: &u32) {
+    env.storage()
+        .persistent()
+        .set(&SalesDataKeys::PrimaryPurchases(address.clone()), purchases);
+}
+
+pub fn get_primary_purchases(env: &Env, address: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&SalesDataKeys::PrimaryPurchases(address.clone()))
+        .unwrap_or(0)
+}
+
+/// The limit of the active sale phase takes precedence over the limit of the collection
+pub fn get_active_max_per_wallet(env: &Env) -> Option<u32> {
+    match get_active_sale_phase(env).and_then(|phase| phase.max_per_wallet) {
+        Some(max) => Some(max),
+        None => get_max_per_wallet(env),
+    }
+}
+
+/// Counts a primary purchase of the buyer, it panics if the buyer already reached the limit
+pub fn register_primary_purchase(env: &Env, buyer: &Address) {
+    let purchases: u32 = get_primary_purchases(env, buyer);
+    if let Some(max) = get_active_max_per_wallet(env) {
+        if purchases >= max {
+            panic_with_error!(env, &SCErrors::WalletLimitReached);
+        }
+    }
+
+    write_primary_purchases(env, buyer, &(purchases + 1));
+    bump_primary_purchases(env, buyer);
+}
+
+pub fn write_reserved_items(env: &Env, reserved_items: &Vec<ReservedItems>) {
+    env.storage()
+        .instance()
+        .set(&SalesDataKeys::ReservedItems, reserved_items);
+}
+
+pub fn get_reserved_items(env: &Env) -> Vec<ReservedItems> {
+    env.storage()
+        .instance()
+        .get(&SalesDataKeys::ReservedItems)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn is_reserved_item(env: &Env, number: &u64) -> bool {
+    get_reserved_items(env)
+        .iter()
+        .any(|range| range.from <= *number && *number <= range.to)
+}
+
+/// Panics if the active sale phase has an allowlist and the buyer isn't part of it
+pub fn check_allowlist(env: &Env, buyer: &Address, proof: &Option<Vec<BytesN<32>>>) {
+    if let Some(phase) = get_active_sale_phase(env) {
+        if let Allowlist::MerkleRoot(root) = phase.allowlist {
+            let leaf: BytesN<32> = env.crypto().sha256(&buyer.clone().to_xdr(env));
+            let is_allowed: bool = match proof {
+                Some(proof) => verify_merkle_proof(env, &root, &leaf, proof),
+                None => false,
+            };
+
+            if !is_allowed {
+                panic_with_error!(env, &SCErrors::AddressIsNotAllowed);
+            }
+        }
+    }
+}
+
+pub fn write_price_tiers(env: &Env, price_tiers: &Vec<PriceTier>) {
+    env.storage()
+        .instance()
+        .set(&SalesDataKeys::PriceTiers, price_tiers);
+}
+
+pub fn get_price_tiers(env: &Env) -> Vec<PriceTier> {
+    env.storage()
+        .instance()
+        .get(&SalesDataKeys::PriceTiers)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn bump_price_override(env: &Env, item_number: &u64) {
+    if env
+        .storage()
+        .persistent()
+        .has(&SalesDataKeys::PriceOverride(*item_number))
+    {
+        env.storage().persistent().extend_ttl(
+            &SalesDataKeys::PriceOverride(*item_number),
+            PRICE_OVERRIDES_BUMP_CONSTANT_THRESHOLD,
+            PRICE_OVERRIDES_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn write_price_override(env: &Env, item_number: &u64, price: &Option<u128>) {
+    match price {
+        Some(price) => env
+            .storage()
+            .persistent()
+            .set(&SalesDataKeys::PriceOverride(*item_number), price),
+        None => env
+            .storage()
+            .persistent()
+            .remove(&SalesDataKeys::PriceOverride(*item_number)),
+    }
+}
+
+pub fn get_price_override(env: &Env, item_number: &u64) -> Option<u128> {
+    env.storage()
+        .persistent()
+        .get(&SalesDataKeys::PriceOverride(*item_number))
+}
+
+/// The price of an Item that hasn't been minted yet, it's resolved in this order:
+/// - The price override of the Item
+/// - The price of the active sale phase if it has one, phases apply the same price to every Item so they take
+///   precedence over tiers
+/// - The price of the tier that includes the Item
+/// - The price of the bonding curve for the number of Items already minted
+/// - The current price of the Dutch auction, it can't be set at the same time as the bonding curve
+/// - The initial price
+pub fn get_primary_price(env: &Env, core_data: &CoreData, item_number: &u64) -> u128 {
+    if let Some(price) = get_price_override(env, item_number) {
+        bump_price_override(env, item_number);
+        return price;
+    }
+
+    if let Some(price) = get_active_sale_phase(env).and_then(|phase| phase.price) {
+        return price;
+    }
+
+    for tier in get_price_tiers(env).iter() {
+        if tier.from <= *item_number && *item_number <= tier.to {
+            return tier.price;
+        }
+    }
+
+    if let Some(bonding_curve) = get_bonding_curve(env) {
+        return bonding_curve_price(env, &bonding_curve, get_minted_count(env));
+    }
+
+    match get_dutch_auction(env) {
+        Some(dutch_auction) => dutch_auction_price(env, &dutch_auction),
+        None => core_data.initial_price,
+    }
+}
+
+pub fn write_refund_window(env: &Env, ledgers: &u32) {
+    env.storage()
+        .instance()
+        .set(&SalesDataKeys::RefundWindow, ledgers);
+}
+
+pub fn get_refund_window(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&SalesDataKeys::RefundWindow)
+        .unwrap_or(0)
+}
+
+pub fn bump_escrowed_sale(env: &Env, item_number: &u64) {
+    if env
+        .storage()
+        .persistent()
+        .has(&SalesDataKeys::EscrowedSale(*item_number))
+    {
+        env.storage().persistent().extend_ttl(
+            &SalesDataKeys::EscrowedSale(*item_number),
+            ESCROWED_SALES_BUMP_CONSTANT_THRESHOLD,
+            ESCROWED_SALES_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn write_escrowed_sale(env: &Env, escrowed_sale: &EscrowedSale) {
+    env.storage().persistent().set(
+        &SalesDataKeys::EscrowedSale(escrowed_sale.item_number),
+        escrowed_sale,
+    );
+}
+
+pub fn remove_escrowed_sale(env: &Env, item_number: &u64) {
+    env.storage()
+        .persistent()
+        .remove(&SalesDataKeys::EscrowedSale(*item_number));
+}
+
+pub fn get_escrowed_sale(env: &Env, item_number: &u64) -> EscrowedSale {
+    match env
+        .storage()
+        .persistent()
+        .get(&SalesDataKeys::EscrowedSale(*item_number))
+    {
+        Some(escrowed_sale) => escrowed_sale,
+        None => panic_with_error!(env, &SCErrors::EscrowedSaleDoesNotExist),
+    }
+}
+
+/// Payments are grouped by recipient so each Address receives a single transfer even if it gets paid for many Items
+pub fn add_payout(payouts: &mut Map<Address, u128>, to: &Address, amount: u128) {
+    let current: u128 = payouts.get(to.clone()).unwrap_or(0);
+    payouts.set(to.clone(), current + amount);
+}
+
+pub fn send_payouts(currency: &token::Client, from: &Address, payouts: &Map<Address, u128>) {
+    for (to, amount) in payouts.iter() {
+        if amount > 0 {
+            currency.transfer(from, &to, &(amount as i128));
+        }
+    }
+}
+
+/// Adds the royalties and the payment of the seller (the price minus the royalties) to the payouts
+pub fn collect_sale_payouts(
+    env: &Env,
+    royalties: &Vec<Royalty>,
+    payouts: &mut Map<Address, u128>,
+    seller: &Address,
+    item_number: &u64,
+    price: &u128,
+    is_first_sale: bool,
+) {
+    let royalties_distributed: u128 =
+        collect_royalties(env, royalties, payouts, item_number, price, is_first_sale);
+    add_payout(payouts, seller, price - royalties_distributed);
+}
+
+/// Adds the payouts of a sale of several Items at once (bundles, swaps)
+/// If none of the Items has its own royalties, the collection royalties are paid once over the total price and the
+/// royalty events use the first Item. Otherwise the price is split evenly between the Items so each one pays its own
+/// royalties over its share, the remainder of the division is added to the first Item
+pub fn collect_split_sale_payouts(
+    env: &Env,
+    payouts: &mut Map<Address, u128>,
+    seller: &Address,
+    item_numbers: &Vec<u64>,
+    price: &u128,
+) {
+    let has_overrides: bool = item_numbers
+        .iter()
+        .any(|item_number| get_item_royalties_override(env, &item_number).is_some());
+
+    if !has_overrides {
+        collect_sale_payouts(
+            env,
+            &get_royalties(env),
+            payouts,
+            seller,
+            &item_numbers.first_unchecked(),
+            price,
+            false,
+        );
+        return;
+    }
+
+    let share: u128 = price / item_numbers.len() as u128;
+    let remainder: u128 = price - share * item_numbers.len() as u128;
+
+    for (index, item_number) in item_numbers.iter().enumerate() {
+        let item_price: u128 = if index == 0 { share + remainder } else { share };
+        collect_sale_payouts(
+            env,
+            &get_item_royalties(env, &item_number),
+            payouts,
+            seller,
+            &item_number,
+            &item_price,
+            false,
+        );
+    }
+}
+
+/// Adds the payments of a primary sale to the payouts, while the refund window is open the payment stays in the
+/// contract along with the royalties the Item has at this moment so later royalty updates don't change the split
+pub fn collect_primary_payouts(
+    env: &Env,
+    royalties: &Vec<Royalty>,
+    payouts: &mut Map<Address, u128>,
+    seller: &Address,
+    buyer: &Address,
+    item_number: &u64,
+    price: &u128,
+) {
+    let refund_window: u32 = get_refund_window(env);
+    if refund_window == 0 {
+        collect_sale_payouts(env, royalties, payouts, seller, item_number, price, true);
+        return;
+    }
+
+    add_payout(payouts, &env.current_contract_address(), *price);
+    write_escrowed_sale(
+        env,
+        &EscrowedSale {
+            item_number: *item_number,
+            buyer: buyer.clone(),
+            price: *price,
+            refund_deadline: env.ledger().sequence().saturating_add(refund_window),
+            royalties: royalties.clone(),
+        },
+    );
+
+    // The entry must outlive the refund window, otherwise the payment could be archived before anyone releases it
+    let ttl: u32 = refund_window
+        .saturating_add(ESCROWED_SALES_BUMP_CONSTANT)
+        .min(env.storage().max_ttl());
+    env.storage()
+        .persistent()
+        .extend_ttl(&SalesDataKeys::EscrowedSale(*item_number), ttl, ttl);
+}
+
+/// Sells a minted Item using funds the contract is already holding (offers, bids, etc), the royalties and the seller
+/// are paid from the contract and the Item is moved to the buyer
+pub fn settle_escrowed_sale(env: &Env, item: &mut Item, buyer: &Address, price: &u128) {
+    let core_data: CoreData = get_core_data(env);
+
+    let mut payouts: Map<Address, u128> = Map::new(env);
+    collect_sale_payouts(
+        env,
+        &get_item_royalties(env, &item.number),
+        &mut payouts,
+        &item.owner,
+        &item.number,
+        price,
+        false,
+    );
+
+    send_payouts(
+        &collection_currency(env, &core_data),
+        &env.current_contract_address(),
+        &payouts,
+    );
+
+    change_owner(env, item, buyer);
+}
+
+/// Validates and executes the purchase of an Item that is for sale, if the Item hasn't been minted yet this is its
+/// "first sale" and the payment goes to the initial seller
+/// The payments are added to the payouts instead of being sent so the caller can send them all at once
+/// It returns the price paid for the Item
+pub fn purchase_item(
+    env: &Env,
+    core_data: &CoreData,
+    royalties: &Vec<Royalty>,
+    payouts: &mut Map<Address, u128>,
+    buyer: &Address,
+    item_number: &u64,
+    proof: &Option<Vec<BytesN<32>>>,
+) -> u128 {
+    if !is_valid_item_number(core_data, item_number) {
+        panic_with_error!(env, &SCErrors::ItemNumberIsInvalid);
+    }
+
+    let is_minted_val: bool = is_minted(env, item_number);
+
+    let (seller, price): (Address, u128) = if is_minted_val {
+        let mut item: Item = get_item(env, item_number);
+
+        if is_listing_expired(env, item_number) {
+            panic_with_error!(env, &SCErrors::ListingHasExpired);
+        }
+
+        if !is_item_for_sale(env, item_number) {
+            panic_with_error!(env, &SCErrors::ItemIsNotForSale);
+        }
+
+        if !can_buy_listing(env, item_number, buyer) {
+            panic_with_error!(env, &SCErrors::ItemIsReservedForAnotherBuyer);
+        }
+
+        let seller: Address = item.owner.clone();
+        let price: u128 = item.price;
+        change_owner(env, &mut item, buyer);
+
+        (seller, price)
+    } else {
+        if !is_primary_sale_open(env) {
+            panic_with_error!(env, &SCErrors::PrimarySaleIsNotOpen);
+        }
+
+        if !is_item_for_sale(env, item_number) {
+            panic_with_error!(env, &SCErrors::ItemIsNotForSale);
+        }
+
+        check_allowlist(env, buyer, proof);
+        register_primary_purchase(env, buyer);
+
+        let price: u128 = get_primary_price(env, core_data, item_number);
+        mint_item(env, item_number, buyer);
+
+        (core_data.initial_seller.clone(), price)
+    };
+
+    // The collection royalties are loaded once by the caller, Items with their own royalties use those instead
+    let item_royalties: Vec<Royalty> =
+        get_item_royalties_override(env, item_number).unwrap_or(royalties.clone());
+    if is_minted_val {
+        collect_sale_payouts(
+            env,
+            &item_royalties,
+            payouts,
+            &seller,
+            item_number,
+            &price,
+            false,
+        );
+    } else {
+        collect_primary_payouts(
+            env,
+            &item_royalties,
+            payouts,
+            &seller,
+            buyer,
+            item_number,
+            &price,
+        );
+    }
+
+    bump_item(env, item_number);
+
+    events::buy(env, seller, buyer.clone(), *item_number, price);
+
+    price
+}
@@ -0,0 +1,61 @@
+use crate::bumps::{SWAPS_BUMP_CONSTANT, SWAPS_BUMP_CONSTANT_THRESHOLD};
+use crate::errors::SCErrors;
+use crate::storage::swaps::{Swap, SwapRoyaltyPolicy, SwapsDataKeys};
+use soroban_sdk::{panic_with_error, Env};
+
+pub fn bump_swap(env: &Env, id: &u64) {
+    if env.storage().persistent().has(&SwapsDataKeys::Swap(*id)) {
+        env.storage().persistent().extend_ttl(
+            &SwapsDataKeys::Swap(*id),
+            SWAPS_BUMP_CONSTANT_THRESHOLD,
+            SWAPS_BUMP_CONSTANT,
+        );
+    }
+}
+
+pub fn get_swap(env: &Env, id: &u64) -> Swap {
+    if env.storage().persistent().has(&SwapsDataKeys::Swap(*id)) {
+        env.storage()
+            .persistent()
+            .get(&SwapsDataKeys::Swap(*id))
+            .unwrap()
+    } else {
+        panic_with_error!(env, &SCErrors::SwapDoesNotExist);
+    }
+}
+
+pub fn write_swap(env: &Env, swap: &Swap) {
+    env.storage()
+        .persistent()
+        .set(&SwapsDataKeys::Swap(swap.id), swap);
+}
+
+pub fn remove_swap(env: &Env, id: &u64) {
+    env.storage().persistent().remove(&SwapsDataKeys::Swap(*id));
+}
+
+pub fn next_swap_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&SwapsDataKeys::LastSwapId)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&SwapsDataKeys::LastSwapId, &id);
+    id
+}
+
+pub fn write_swap_royalty_policy(env: &Env, policy: &SwapRoyaltyPolicy) {
+    env.storage()
+        .instance()
+        .set(&SwapsDataKeys::RoyaltyPolicy, policy);
+}
+
+pub fn get_swap_royalty_policy(env: &Env) -> SwapRoyaltyPolicy {
+    env.storage()
+        .instance()
+        .get(&SwapsDataKeys::RoyaltyPolicy)
+        .unwrap_or(SwapRoyaltyPolicy::NoRoyalties)
+}
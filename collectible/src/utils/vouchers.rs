@@ -0,0 +1,34 @@
+use crate::bumps::{VOUCHERS_BUMP_CONSTANT, VOUCHERS_BUMP_CONSTANT_THRESHOLD};
+use crate::errors::SCErrors;
+use crate::storage::vouchers::VouchersDataKeys;
+use soroban_sdk::{panic_with_error, BytesN, Env};
+
+pub fn write_voucher_signer(env: &Env, public_key: &BytesN<32>) {
+    env.storage()
+        .instance()
+        .set(&VouchersDataKeys::Signer, public_key);
+}
+
+pub fn get_voucher_signer(env: &Env) -> BytesN<32> {
+    match env.storage().instance().get(&VouchersDataKeys::Signer) {
+        Some(public_key) => public_key,
+        None => panic_with_error!(env, &SCErrors::VoucherSignerIsNotDefined),
+    }
+}
+
+pub fn is_nonce_used(env: &Env, nonce: &u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&VouchersDataKeys::UsedNonce(*nonce))
+}
+
+pub fn write_used_nonce(env: &Env, nonce: &u64) {
+    env.storage()
+        .persistent()
+        .set(&VouchersDataKeys::UsedNonce(*nonce), &true);
+    env.storage().persistent().extend_ttl(
+        &VouchersDataKeys::UsedNonce(*nonce),
+        VOUCHERS_BUMP_CONSTANT_THRESHOLD,
+        VOUCHERS_BUMP_CONSTANT,
+    );
+}